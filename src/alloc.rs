@@ -0,0 +1,245 @@
+use std::fmt;
+
+/// Heap usage of a measured piece of code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations included.
+    pub allocations: usize,
+    /// Total bytes requested, a reallocation counting its new size.
+    pub bytes: usize,
+    /// Most bytes live at once, on top of what was live before.
+    pub peak: usize,
+}
+
+impl AllocStats {
+    /// Per-run figures of a measurement that covered `runs` identical runs.
+    pub fn per_run(self, runs: usize) -> Self {
+        let runs = runs.max(1);
+        Self {
+            allocations: self.allocations / runs,
+            bytes: self.bytes / runs,
+            peak: self.peak,
+        }
+    }
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        )
+    }
+}
+
+/// Byte count rendered with a binary unit.
+struct Bytes(usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{:.2} {}", value, UNITS[unit])
+    }
+}
+
+/// Runs `f`, returning its heap usage when built with the `alloc-stats`
+/// feature and `None` otherwise.
+///
+/// Only allocations of the calling thread are counted, so parts measured on
+/// other threads at the same time do not show up, but neither does work `f`
+/// hands to threads of its own.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        let (result, stats) = counting::measure(f);
+        (result, Some(stats))
+    }
+
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use super::AllocStats;
+
+    /// Counters of the current thread. Memory freed by another thread than
+    /// the one allocating it makes `live` drop below zero, hence signed.
+    struct Counters {
+        allocations: Cell<usize>,
+        bytes: Cell<usize>,
+        live: Cell<isize>,
+        peak: Cell<isize>,
+    }
+
+    thread_local! {
+        static COUNTERS: Counters = const {
+            Counters {
+                allocations: Cell::new(0),
+                bytes: Cell::new(0),
+                live: Cell::new(0),
+                peak: Cell::new(0),
+            }
+        };
+    }
+
+    /// The system allocator, counting every allocation on the way through.
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    impl CountingAllocator {
+        fn grow(size: usize) {
+            // the counters are gone while a thread is torn down
+            let _ = COUNTERS.try_with(|counters| {
+                counters.allocations.set(counters.allocations.get() + 1);
+                counters.bytes.set(counters.bytes.get() + size);
+            });
+        }
+
+        fn resize(old: usize, new: usize) {
+            let _ = COUNTERS.try_with(|counters| {
+                let live = counters.live.get() + new as isize - old as isize;
+                counters.live.set(live);
+                counters.peak.set(counters.peak.get().max(live));
+            });
+        }
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                Self::grow(layout.size());
+                Self::resize(0, layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                Self::grow(layout.size());
+                Self::resize(0, layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            Self::resize(layout.size(), 0);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                Self::grow(new_size);
+                Self::resize(layout.size(), new_size);
+            }
+            new_ptr
+        }
+    }
+
+    pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, AllocStats) {
+        let (allocations, bytes, live) = COUNTERS.with(|counters| {
+            counters.peak.set(counters.live.get());
+            (counters.allocations.get(), counters.bytes.get(), counters.live.get())
+        });
+
+        let result = f();
+
+        let stats = COUNTERS.with(|counters| AllocStats {
+            allocations: counters.allocations.get() - allocations,
+            bytes: counters.bytes.get() - bytes,
+            peak: (counters.peak.get() - live).max(0) as usize,
+        });
+        (result, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_stats() {
+        let stats = AllocStats {
+            allocations: 40,
+            bytes: 3 * 1024 * 1024 / 2,
+            peak: 512,
+        };
+        assert_eq!("40 allocations, 1.50 MiB allocated, 512 B peak", stats.to_string());
+        assert_eq!(4, stats.per_run(10).allocations);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn counts_allocations() {
+        let (_, stats) = measure(|| {
+            let first = vec![0u8; 4096];
+            let second = vec![0u8; 1024];
+            first.len() + second.len()
+        });
+
+        let stats = stats.unwrap();
+        assert_eq!(2, stats.allocations);
+        assert_eq!(5120, stats.bytes);
+        assert_eq!(5120, stats.peak);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn counts_parallel_parts_apart() {
+        use std::sync::Barrier;
+        use std::thread;
+
+        let barrier = Barrier::new(2);
+        let part = |size: usize, count: usize| {
+            measure(|| {
+                let buffers = (0..count).map(|_| vec![0u8; size]).collect::<Vec<_>>();
+                // both parts hold their buffers at the same time
+                barrier.wait();
+                buffers.len()
+            })
+            .1
+            .unwrap()
+        };
+
+        let (first, second) = thread::scope(|scope| {
+            let first = scope.spawn(|| part(1024, 3));
+            let second = scope.spawn(|| part(100, 5));
+            (first.join().unwrap(), second.join().unwrap())
+        });
+
+        // the vector of buffers is one more allocation
+        let pointers = std::mem::size_of::<Vec<u8>>();
+        let expected = |allocations, bytes| AllocStats { allocations, bytes, peak: bytes };
+        assert_eq!(expected(4, 3 * 1024 + 3 * pointers), first);
+        assert_eq!(expected(6, 5 * 100 + 5 * pointers), second);
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc-stats"))]
+    fn disabled_without_feature() {
+        assert_eq!((3, None), measure(|| 3));
+    }
+}
@@ -0,0 +1,190 @@
+use anyhow::*;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::records;
+use crate::solution::Part;
+
+/// Where accepted answers are kept unless `--answers` says otherwise.
+pub const DEFAULT_PATH: &str = "answers/2024.toml";
+
+/// Stable 64-bit FNV-1a hash identifying an input, independent of the Rust version.
+pub fn input_hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// How a computed answer compares to the stored one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerStatus {
+    Matches,
+    Differs { expected: usize },
+    New,
+}
+
+impl fmt::Display for AnswerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswerStatus::Matches => write!(f, "✓"),
+            AnswerStatus::Differs { expected } => write!(f, "✗ expected {}", expected),
+            AnswerStatus::New => write!(f, "(new)"),
+        }
+    }
+}
+
+/// Accepted answers per day, input hash and part, stored as a small TOML file:
+///
+/// ```toml
+/// [day05.9a3c51e0d2f47b86]
+/// part1 = 143
+/// part2 = 123
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnswerStore {
+    path: PathBuf,
+    answers: BTreeMap<(u8, u64), BTreeMap<Part, usize>>,
+}
+
+impl AnswerStore {
+    /// Loads the store at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let answers = records::load(&path, "answers", Self::parse)?;
+        Ok(Self { path, answers })
+    }
+
+    fn parse(text: &str) -> Result<BTreeMap<(u8, u64), BTreeMap<Part, usize>>> {
+        let mut answers = BTreeMap::new();
+        let mut table = None;
+
+        for (idx, line) in text.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                let (day, hash) = header
+                    .split_once('.')
+                    .and_then(|(day, hash)| {
+                        let day = day.strip_prefix("day")?.parse::<u8>().ok()?;
+                        let hash = u64::from_str_radix(hash, 16).ok()?;
+                        Some((day, hash))
+                    })
+                    .with_context(|| format!("line {}: expected [dayNN.<input hash>], found {}", line_no, line))?;
+
+                table = Some((day, hash));
+                answers.entry((day, hash)).or_insert_with(BTreeMap::new);
+                continue;
+            }
+
+            let key = table.with_context(|| format!("line {}: answer outside of a [dayNN.<hash>] table", line_no))?;
+            let (part, answer) = line
+                .split_once('=')
+                .and_then(|(part, answer)| {
+                    let part = part.trim().strip_prefix("part")?.parse::<Part>().ok()?;
+                    let answer = answer.trim().parse::<usize>().ok()?;
+                    Some((part, answer))
+                })
+                .with_context(|| format!("line {}: expected partN = <answer>, found {}", line_no, line))?;
+
+            answers.entry(key).or_insert_with(BTreeMap::new).insert(part, answer);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, day: u8, input_hash: u64, part: Part) -> Option<usize> {
+        self.answers.get(&(day, input_hash))?.get(&part).copied()
+    }
+
+    pub fn set(&mut self, day: u8, input_hash: u64, part: Part, answer: usize) {
+        self.answers
+            .entry((day, input_hash))
+            .or_default()
+            .insert(part, answer);
+    }
+
+    pub fn check(&self, day: u8, input_hash: u64, part: Part, answer: usize) -> AnswerStatus {
+        match self.get(day, input_hash, part) {
+            Some(expected) if expected == answer => AnswerStatus::Matches,
+            Some(expected) => AnswerStatus::Differs { expected },
+            None => AnswerStatus::New,
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+
+        fs::write(&self.path, self.to_string())
+            .with_context(|| format!("failed to write {}", self.path.display()))
+    }
+}
+
+impl fmt::Display for AnswerStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Accepted answers, keyed by day and a hash of the puzzle input.")?;
+
+        for ((day, hash), parts) in &self.answers {
+            writeln!(f, "\n[day{:02}.{:016x}]", day, hash)?;
+            for (part, answer) in parts {
+                writeln!(f, "part{} = {}", part, answer)?;
+            }
+        }
+
+        fmt::Result::Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, input_hash(""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, input_hash("a"));
+        assert_ne!(input_hash("1 2\n"), input_hash("1 2"));
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut store = AnswerStore::default();
+        store.set(5, 0xabc, Part::One, 143);
+        store.set(5, 0xabc, Part::Two, 123);
+        store.set(1, 0x1, Part::One, 11);
+
+        let parsed = AnswerStore::parse(&store.to_string()).unwrap();
+        assert_eq!(store.answers, parsed);
+        assert!(store.to_string().contains("[day05.0000000000000abc]\npart1 = 143\npart2 = 123\n"));
+    }
+
+    #[test]
+    fn checks_answers() {
+        let mut store = AnswerStore::default();
+        store.set(5, 7, Part::One, 143);
+
+        assert_eq!(AnswerStatus::Matches, store.check(5, 7, Part::One, 143));
+        assert_eq!(AnswerStatus::Differs { expected: 143 }, store.check(5, 7, Part::One, 140));
+        assert_eq!(AnswerStatus::New, store.check(5, 7, Part::Two, 123));
+        assert_eq!(AnswerStatus::New, store.check(5, 8, Part::One, 143));
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        assert!(AnswerStore::parse("part1 = 3").is_err());
+        assert!(AnswerStore::parse("[day05]\npart1 = 3").is_err());
+        assert!(AnswerStore::parse("[day05.ff]\npart3 = 3").is_err());
+        assert!(AnswerStore::parse("[day05.ff]\npart1 = abc").is_err());
+        assert!(AnswerStore::parse("# comment\n\n[day05.ff] # trailing\npart1 = 3").is_ok());
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::*;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// How many times each part runs in `--bench` mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Untimed runs to warm caches, the first one (which always happens)
+    /// also provides the answer.
+    pub warmup: usize,
+    /// Timed runs the statistics are computed from.
+    pub iterations: usize,
+}
+
+impl BenchConfig {
+    /// Total number of times [`bench`] calls the benchmarked function.
+    pub fn runs(&self) -> usize {
+        self.warmup.max(1) + self.iterations
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 20,
+        }
+    }
+}
+
+/// Summary statistics over the timed runs of a benchmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics, `None` when there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        // nearest-rank percentile
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Some(Self {
+            samples: n,
+            min: sorted[0],
+            median,
+            mean,
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:.2?} | median {:.2?} | mean {:.2?} | p95 {:.2?} | stddev {:.2?}",
+            self.min, self.median, self.mean, self.p95, self.stddev
+        )
+    }
+}
+
+/// Runs `f` for the configured warm-up and timed iterations, returning the
+/// result of the first run together with the timing statistics.
+pub fn bench<T, F: FnMut() -> Result<T>>(config: &BenchConfig, mut f: F) -> Result<(T, Stats)> {
+    ensure!(config.iterations > 0, "benchmarks need at least one iteration");
+
+    let result = f()?;
+    for _ in 1..config.warmup {
+        f()?;
+    }
+
+    let mut samples = Vec::with_capacity(config.iterations);
+    for _ in 0..config.iterations {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+
+    let stats = Stats::from_samples(&samples).expect("at least one sample");
+    Ok((result, stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let samples = [ms(4), ms(2), ms(8), ms(6)];
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(4, stats.samples);
+        assert_eq!(ms(2), stats.min);
+        assert_eq!(ms(5), stats.median);
+        assert_eq!(ms(5), stats.mean);
+        assert_eq!(ms(8), stats.p95);
+        assert_eq!(Duration::from_secs_f64(5f64.sqrt() / 1000.0), stats.stddev);
+
+        assert_eq!(None, Stats::from_samples(&[]));
+    }
+
+    #[test]
+    fn p95_uses_nearest_rank() {
+        let samples = (1..=100).map(ms).collect::<Vec<_>>();
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(ms(95), stats.p95);
+        assert_eq!(Duration::from_micros(50_500), stats.median);
+    }
+
+    #[test]
+    fn runs_warmup_and_iterations() {
+        let mut calls = 0;
+        let config = BenchConfig {
+            warmup: 2,
+            iterations: 5,
+        };
+
+        let (answer, stats) = bench(&config, || {
+            calls += 1;
+            Ok(calls)
+        })
+        .unwrap();
+
+        assert_eq!(1, answer);
+        assert_eq!(7, calls);
+        assert_eq!(config.runs(), calls);
+        assert_eq!(5, stats.samples);
+        assert!(bench(&BenchConfig { warmup: 0, iterations: 0 }, || Ok(0)).is_err());
+    }
+}
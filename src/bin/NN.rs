@@ -1,50 +1,33 @@
 use anyhow::*;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use code_timing_macros::time_snippet;
-use const_format::concatcp;
 use adv_code_2024::*;
 
-const DAY: &str = "NN"; // TODO: Fill the day
-const INPUT_FILE: &str = concatcp!("input/", DAY, ".txt");
+pub struct DayNN;
 
-const TEST: &str = "\
-<TEST-INPUT>
-"; // TODO: Add the test input
+impl Solution for DayNN {
+    const DAY: u8 = 0; // TODO: Fill the day
+    const TITLE: &'static str = "<TITLE>"; // TODO: Fill the puzzle title
 
-fn main() -> Result<()> {
-    start_day(DAY);
+    type Input = Vec<String>;
 
-    //region Part 1
-    println!("=== Part 1 ===");
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
 
-    fn part1<R: BufRead>(reader: R) -> Result<usize> {
+    fn part1(input: &Self::Input) -> Result<usize> {
         // TODO: Solve Part 1 of the puzzle
-        let answer = reader.lines().flatten().count();
+        let answer = input.len();
         Ok(answer)
     }
 
-    // TODO: Set the expected answer for the test input
-    assert_eq!(0, part1(BufReader::new(TEST.as_bytes()))?);
-
-    let input_file = BufReader::new(File::open(INPUT_FILE)?);
-    let result = time_snippet!(part1(input_file)?);
-    println!("Result = {}", result);
-    //endregion
-
-    //region Part 2
-    // println!("\n=== Part 2 ===");
-    //
-    // fn part2<R: BufRead>(reader: R) -> Result<usize> {
-    //     Ok(0)
-    // }
-    //
-    // assert_eq!(0, part2(BufReader::new(TEST.as_bytes()))?);
-    //
-    // let input_file = BufReader::new(File::open(INPUT_FILE)?);
-    // let result = time_snippet!(part2(input_file)?);
-    // println!("Result = {}", result);
-    //endregion
-
-    Ok(())
+    fn part2(_input: &Self::Input) -> Result<usize> {
+        Ok(0)
+    }
+}
+
+// TODO: Add the example input as examples/NN-1.txt and its expected answers
+//       as examples/NN-1.expected, e.g. `part1 = 0`, or let `aoc examples NN`
+//       extract them from the puzzle page
+
+fn main() -> Result<()> {
+    run_day::<DayNN>()
 }
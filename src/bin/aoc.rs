@@ -0,0 +1,557 @@
+use anyhow::*;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::SystemTime;
+use adv_code_2024::{days, examples, input, ledger, puzzle, scaffold, watch};
+use adv_code_2024::answers::{input_hash, AnswerStatus, AnswerStore};
+use adv_code_2024::client::{Client, Outcome};
+use adv_code_2024::history::History;
+use adv_code_2024::input::InputSource;
+use adv_code_2024::ledger::{Ledger, Rejection};
+use adv_code_2024::report::Format;
+use adv_code_2024::runner::{self, Options};
+use adv_code_2024::Part;
+
+const USAGE: &str = "\
+Usage: aoc run <DAYS> [--part 1|2] [--input PATH] [--answers PATH] [--record] [--format F]
+               [--timeout T] [--jobs N | --serial] [BENCH]
+       aoc run --all [--part 1|2] [--answers PATH] [--record] [--format F] [--timeout T]
+               [--jobs N | --serial] [BENCH]
+       aoc compare [--baseline REV] [--threshold PERCENT] [--history PATH]
+       aoc watch <DAY> [--part 1|2] [--input PATH] [--answers PATH] [--interval T]
+       aoc fetch <DAYS> [--base-url URL] [--session-file PATH]
+       aoc puzzle <DAYS> [--html PATH] [--base-url URL] [--session-file PATH]
+       aoc examples <DAY> [--html PATH] [--block N[,N...]] [--base-url URL]
+               [--session-file PATH]
+       aoc new <DAY> [--title TITLE]
+       aoc submit <DAY> <PART> [ANSWER] [--input PATH] [--answers PATH] [--base-url URL]
+               [--session-file PATH]
+
+DAYS is a single day (5), a range (1-8) or a list of both (1,3,5-7).
+Inputs are read from $AOC_INPUT_DIR/NN.txt, or input/NN.txt when it is not set.
+--input reads a single day's input from PATH instead, - meaning stdin.
+
+Answers are checked against answers/2024.toml (or --answers PATH) and shown
+as ✓ when they match, ✗ when they differ and (new) when none is stored yet.
+--record stores this run's answers as the accepted ones.
+
+--format text|json|csv|junit|markdown picks the output. json and csv print
+one record per part with day, title, part, answer, status (matches, differs,
+new or error), expected answer, parse and solve time in nanoseconds and the
+error message. junit prints a JUnit XML report with a test case per part that
+fails when the answer differs from the stored one, markdown a table of the
+answers, times and stars per day.
+
+--timeout T parses the input and runs every part on a worker thread and reports
+it as TIMEOUT once it takes longer than T (10, 2.5s, 500ms or 1m), the remaining
+days still run.
+
+Days and parts run in parallel on --jobs N worker threads, one per core by
+default, and are printed in order. --serial runs them one after another, as
+does BENCH unless --jobs is given, which keeps timings undisturbed.
+
+BENCH is --bench [--warmup N] [--iterations N]: parsing and every part run N
+times after the warm-up runs (3 and 20 by default) and min, median, mean, p95
+and standard deviation of their times are reported. The results are
+appended to benchmarks/history.csv (or --history PATH) with the time, git
+revision and input hash.
+
+watch checks a day's examples and input every time input/NN.txt (or --input),
+one of its examples/NN-* files or src/days/dayNN.rs changes, polling every
+--interval (500ms by default). Source changes rebuild and restart the runner.
+Each check prints pass or fail per example and the answers, with the previous
+answer next to any that changed.
+
+fetch downloads the inputs of DAYS to $AOC_INPUT_DIR/NN.txt or input/NN.txt,
+skipping the ones already there and days that are not unlocked yet. It logs in
+with the session cookie from $AOC_SESSION, --session-file PATH,
+$AOC_SESSION_FILE or .aoc-session and talks to --base-url URL, $AOC_BASE_URL
+or https://adventofcode.com. $AOC_CONTACT is added to the User-Agent.
+
+puzzle converts the puzzle descriptions of DAYS to Markdown in puzzles/NN.md,
+from the pages on the site or from a saved page with --html PATH for a single
+day. Part two is included once it is unlocked, run it again after solving
+part one.
+
+examples adds the example inputs of a puzzle page, from the site or a saved
+page with --html PATH, to examples/NN-<n>.txt, with the emphasized answers from
+the description in examples/NN-<n>.expected for the tests and watch to check.
+When the page has several <pre> blocks it lists them and asks which ones are
+examples, --block N[,N...] picks them up front. Blocks that are examples
+already are skipped.
+
+new creates a day from the src/bin/NN.rs template: src/days/dayNN.rs, its
+binary src/bin/NN.rs and an empty examples/NN-1.txt, registers it in
+src/days/mod.rs and ticks it in the README. The title is --title or the one of
+the archived puzzle description, a TODO otherwise. Existing days are left alone.
+
+watch, examples and new work on the crate in the current directory, or the
+closest one above it with a Cargo.toml, not on the one the runner was built in.
+
+submit posts ANSWER, or the part's answer for the day's input when it is left
+out, and reports whether it was correct, too high, too low or wrong, or that
+the site wants you to wait or the part is already solved. Correct answers are
+stored in the answers file, answers that are stored already are not submitted.
+Rejected answers are kept in answers/2024.rejected.csv (next to the answers
+file), and an answer that was rejected before or lies outside the range the
+too high and too low ones imply is refused without submitting it. It uses the
+same session and base URL as fetch.
+
+compare checks the parse time and every part of the latest benchmark run
+against the previous result for the same input, or the latest one from a git
+revision starting with --baseline REV, and fails if any got slower by more than
+--threshold percent (10 by default).";
+
+/// Default for `compare --threshold`, in percent.
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+fn run(args: Vec<String>) -> Result<bool> {
+    let (options, rest) = Options::parse_args(args)?;
+
+    let days = match rest.as_slice() {
+        [all] if all == "--all" => days::DAYS.iter().map(|solution| solution.day()).collect(),
+        [spec] if !spec.starts_with("--") => runner::parse_days(spec)?,
+        _ => bail!("expected a day selection or --all\n\n{}", USAGE),
+    };
+
+    ensure!(
+        days.len() == 1 || options.input == InputSource::Default,
+        "--input can only be used when running a single day"
+    );
+
+    let solutions = days
+        .iter()
+        .map(|&day| days::get(day).with_context(|| format!("Day {:02} is not implemented", day)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let reports = runner::run(&solutions, &options)?;
+    if options.format == Format::Text {
+        runner::print_summary(&reports);
+    }
+
+    Ok(reports.iter().all(|report| report.is_ok()))
+}
+
+/// Splits the arguments left over by [`Options::parse_args`] into the values
+/// of the subcommand's own `flags` and the positional arguments.
+fn take_flags(rest: Vec<String>, flags: &[&str]) -> Result<(BTreeMap<String, String>, Vec<String>)> {
+    let mut values = BTreeMap::new();
+    let mut positional = Vec::new();
+
+    let mut rest = rest.into_iter();
+    while let Some(arg) = rest.next() {
+        if !arg.starts_with("--") {
+            positional.push(arg);
+            continue;
+        }
+
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg.clone(), None),
+        };
+        ensure!(flags.contains(&flag.as_str()), "unexpected argument '{}'\n\n{}", arg, USAGE);
+
+        let value = inline_value
+            .or_else(|| rest.next())
+            .with_context(|| format!("missing value for {}", flag))?;
+        values.insert(flag, value);
+    }
+
+    Ok((values, positional))
+}
+
+fn compare(args: Vec<String>) -> Result<bool> {
+    let (options, rest) = Options::parse_args(args)?;
+    let (flags, positional) = take_flags(rest, &["--baseline", "--threshold"])?;
+    ensure!(positional.is_empty(), "unexpected arguments: {}\n\n{}", positional.join(" "), USAGE);
+
+    let baseline = flags.get("--baseline").cloned();
+    let threshold = match flags.get("--threshold") {
+        Some(value) => value
+            .trim_end_matches('%')
+            .parse()
+            .with_context(|| format!("invalid threshold '{}', expected a percentage", value))?,
+        None => DEFAULT_THRESHOLD,
+    };
+
+    let history = History::load(options.history_path())?;
+    let Some(latest) = history.latest_run().first() else {
+        bail!("no benchmark results in {}, run with --bench first", history.path().display());
+    };
+    println!(
+        "Comparing run {} of {} against {}, threshold {}%",
+        latest.timestamp,
+        latest.rev,
+        baseline.as_deref().map_or("the previous results".to_string(), |rev| format!("revision {}", rev)),
+        threshold
+    );
+
+    let comparisons = history.compare(baseline.as_deref());
+    let mut rows = vec![["Day", "Step", "Baseline", "Rev", "Latest", "Change", ""]
+        .map(String::from)
+        .to_vec()];
+    for comparison in &comparisons {
+        let latest = &comparison.latest;
+        let (time, rev) = match &comparison.baseline {
+            Some(baseline) => (format!("{:.2?}", baseline.stats.median), baseline.rev.clone()),
+            None => ("-".to_string(), "-".to_string()),
+        };
+
+        rows.push(vec![
+            format!("{:02}", latest.day),
+            latest.step.to_string(),
+            time,
+            rev,
+            format!("{:.2?}", latest.stats.median),
+            comparison.change().map_or("-".to_string(), |change| format!("{:+.1}%", change)),
+            if comparison.is_regression(threshold) { "SLOWER" } else { "" }.to_string(),
+        ]);
+    }
+    runner::print_table("Comparison", &rows);
+
+    let regressions = comparisons.iter().filter(|comparison| comparison.is_regression(threshold)).count();
+    if regressions > 0 {
+        println!("\n{} step(s) got slower by more than {}%", regressions, threshold);
+    }
+
+    Ok(regressions == 0)
+}
+
+fn watch(args: Vec<String>) -> Result<bool> {
+    let (options, rest) = Options::parse_args(args.clone())?;
+    let (flags, positional) = take_flags(rest, &["--interval", "--previous"])?;
+
+    let day = match positional.as_slice() {
+        [day] => runner::parse_days(day)?,
+        _ => bail!("expected a single day\n\n{}", USAGE),
+    };
+    let solution = match day.as_slice() {
+        [day] => days::get(*day).with_context(|| format!("Day {:02} is not implemented", day))?,
+        _ => bail!("watch runs a single day\n\n{}", USAGE),
+    };
+
+    let interval = match flags.get("--interval") {
+        Some(interval) => runner::parse_duration(interval)?,
+        None => watch::DEFAULT_INTERVAL,
+    };
+    let previous = match flags.get("--previous") {
+        Some(previous) => watch::parse_previous(previous)?,
+        None => BTreeMap::new(),
+    };
+
+    // restarts pass the answers seen so far, drop the ones we were given
+    let mut args = args.into_iter();
+    let mut restart_args = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--previous" => {
+                args.next();
+            }
+            arg if arg.starts_with("--previous=") => {}
+            _ => restart_args.push(arg),
+        }
+    }
+
+    watch::watch(&scaffold::crate_root()?, solution, &options, interval, previous, &restart_args)?;
+    Ok(true)
+}
+
+fn fetch(args: Vec<String>) -> Result<bool> {
+    let (flags, positional) = take_flags(args, &["--base-url", "--session-file"])?;
+    let days = match positional.as_slice() {
+        [spec] => runner::parse_days(spec)?,
+        _ => bail!("expected a day selection\n\n{}", USAGE),
+    };
+
+    let client = Client::from_env(
+        flags.get("--base-url").cloned(),
+        flags.get("--session-file").map(PathBuf::from),
+    )?;
+
+    let mut ok = true;
+    for day in days {
+        let path = input::default_path(day);
+        match client.fetch_input(day, &path, SystemTime::now()) {
+            Result::Ok(true) => println!("Day {:02}: downloaded to {}", day, path.display()),
+            Result::Ok(false) => println!("Day {:02}: cached at {}", day, path.display()),
+            Err(err) => {
+                println!("Day {:02}: {:#}", day, err);
+                ok = false;
+            }
+        }
+    }
+
+    Ok(ok)
+}
+
+fn puzzle(args: Vec<String>) -> Result<bool> {
+    let (flags, positional) = take_flags(args, &["--html", "--base-url", "--session-file"])?;
+    let days = match positional.as_slice() {
+        [spec] => runner::parse_days(spec)?,
+        _ => bail!("expected a day selection\n\n{}", USAGE),
+    };
+
+    let pages = match flags.get("--html") {
+        Some(path) => {
+            ensure!(days.len() == 1, "--html can only be used for a single day");
+            let page = fs::read_to_string(path).with_context(|| format!("failed to read {}", path))?;
+            vec![(days[0], Ok(page))]
+        }
+        None => {
+            let client = Client::from_env(
+                flags.get("--base-url").cloned(),
+                flags.get("--session-file").map(PathBuf::from),
+            )?;
+            days.iter()
+                .map(|&day| (day, client.download_puzzle(day, SystemTime::now())))
+                .collect()
+        }
+    };
+
+    let mut ok = true;
+    for (day, page) in pages {
+        let path = puzzle::default_path(day);
+        match page.and_then(|page| puzzle::archive(&page, &path)) {
+            Result::Ok(parts) => println!("Day {:02}: wrote {} part(s) to {}", day, parts, path.display()),
+            Err(err) => {
+                println!("Day {:02}: {:#}", day, err);
+                ok = false;
+            }
+        }
+    }
+
+    Ok(ok)
+}
+
+/// Asks on stdin which of the numbered `blocks` to use, the first one by default.
+fn choose_blocks(blocks: &[puzzle::ExampleBlock]) -> Result<Vec<usize>> {
+    println!("The page has {} blocks:", blocks.len());
+    for (idx, block) in blocks.iter().enumerate() {
+        let first = block.input.lines().next().unwrap_or_default();
+        let preview = first.chars().take(40).collect::<String>();
+        println!(
+            "{:>4}. part {}, {} line(s): {}{}",
+            idx + 1,
+            block.part,
+            block.input.lines().count(),
+            preview,
+            if preview.len() < first.len() { "..." } else { "" }
+        );
+    }
+    print!("Which ones are examples? [1] ");
+    io::stdout().flush()?;
+
+    let mut choice = String::new();
+    io::stdin().lock().read_line(&mut choice)?;
+    parse_blocks(choice.trim(), blocks.len())
+}
+
+/// Parses a `1,3` style list of block numbers, empty meaning the first one.
+fn parse_blocks(choice: &str, count: usize) -> Result<Vec<usize>> {
+    if choice.is_empty() {
+        return Ok(vec![0]);
+    }
+
+    choice
+        .split(',')
+        .map(|number| {
+            let number = number.trim();
+            match number.parse::<usize>() {
+                Result::Ok(idx @ 1..) if idx <= count => Ok(idx - 1),
+                _ => bail!("invalid block '{}', expected a number from 1 to {}", number, count),
+            }
+        })
+        .collect()
+}
+
+fn examples(args: Vec<String>) -> Result<bool> {
+    let (flags, positional) = take_flags(args, &["--html", "--block", "--base-url", "--session-file"])?;
+    let day = match positional.as_slice() {
+        [day] => match runner::parse_days(day)?.as_slice() {
+            [day] => *day,
+            _ => bail!("examples takes a single day\n\n{}", USAGE),
+        },
+        _ => bail!("expected a single day\n\n{}", USAGE),
+    };
+
+    let page = match flags.get("--html") {
+        Some(path) => fs::read_to_string(path).with_context(|| format!("failed to read {}", path))?,
+        None => Client::from_env(
+            flags.get("--base-url").cloned(),
+            flags.get("--session-file").map(PathBuf::from),
+        )?
+        .download_puzzle(day, SystemTime::now())?,
+    };
+
+    let blocks = puzzle::example_blocks(&page);
+    ensure!(!blocks.is_empty(), "the Day {:02} page has no example blocks", day);
+    let chosen = match flags.get("--block") {
+        Some(choice) => parse_blocks(choice, blocks.len())?,
+        None if blocks.len() == 1 => vec![0],
+        None => choose_blocks(&blocks)?,
+    };
+
+    let answers = puzzle::expected_answers(&page);
+    let assigned = puzzle::assign_answers(&chosen.iter().map(|&idx| &blocks[idx]).collect::<Vec<_>>(), &answers);
+
+    let dir = watch::examples_dir(&scaffold::crate_root()?);
+    let existing = examples::load(&dir, day)?;
+    for (idx, expected) in chosen.into_iter().zip(assigned) {
+        let block = &blocks[idx];
+        let input = input::normalize(&block.input);
+        if let Some(example) = existing.iter().find(|example| example.input == input) {
+            println!("Block {} is already {}", idx + 1, example.path.display());
+            continue;
+        }
+
+        let path = examples::save(&dir, day, &input, &expected)?;
+
+        let expected = expected
+            .iter()
+            .map(|(part, answer)| format!("part {} = {}", part, answer))
+            .collect::<Vec<_>>();
+        println!(
+            "Block {}: wrote {}{}",
+            idx + 1,
+            path.display(),
+            if expected.is_empty() {
+                ", no expected answers found".to_string()
+            } else {
+                format!(", expecting {}", expected.join(" and "))
+            }
+        );
+    }
+
+    Ok(true)
+}
+
+fn new(args: Vec<String>) -> Result<bool> {
+    let (flags, positional) = take_flags(args, &["--title"])?;
+    let day = match positional.as_slice() {
+        [day] => day
+            .parse::<u8>()
+            .with_context(|| format!("invalid day '{}', expected a number from 1 to 25", day))?,
+        _ => bail!("expected a single day\n\n{}", USAGE),
+    };
+
+    let root = scaffold::crate_root()?;
+    let title = flags.get("--title").cloned().or_else(|| scaffold::archived_title(&root, day));
+    let scaffold = scaffold::create(&root, day, title.as_deref())?;
+
+    println!("Created Day {:02}{}:", day, title.map_or(String::new(), |title| format!(" ({})", title)));
+    for file in &scaffold.files {
+        println!("  {}", file.strip_prefix(&root).unwrap_or(file).display());
+    }
+    if !scaffold.ticked {
+        println!("The README checklist has no open entry for Day {}", day);
+    }
+
+    Ok(true)
+}
+
+fn submit(args: Vec<String>) -> Result<bool> {
+    let (mut options, rest) = Options::parse_args(args)?;
+    let (flags, positional) = take_flags(rest, &["--base-url", "--session-file"])?;
+    ensure!(options.part.is_none(), "pass the part as PART, not --part\n\n{}", USAGE);
+
+    let (day, part, answer) = match positional.as_slice() {
+        [day, part] => (day, part, None),
+        [day, part, answer] => (day, part, Some(answer)),
+        _ => bail!("expected a day, a part and optionally the answer\n\n{}", USAGE),
+    };
+    let day = match runner::parse_days(day)?.as_slice() {
+        [day] => *day,
+        _ => bail!("submit takes a single day\n\n{}", USAGE),
+    };
+    let part = part.parse::<Part>()?;
+
+    let client = Client::from_env(
+        flags.get("--base-url").cloned(),
+        flags.get("--session-file").map(PathBuf::from),
+    )?;
+    let mut store = AnswerStore::load(options.answers_path())?;
+    let mut ledger = Ledger::load(ledger::default_path(&options.answers_path()))?;
+
+    let (answer, hash) = match answer {
+        Some(answer) => (
+            answer
+                .parse::<usize>()
+                .with_context(|| format!("invalid answer '{}', expected a number", answer))?,
+            input_hash(&options.input.read(day)?),
+        ),
+        None => {
+            let solution = days::get(day).with_context(|| format!("Day {:02} is not implemented", day))?;
+            options.part = Some(part);
+            let report = runner::run_one(solution, &options, &store);
+            runner::print_day(&report);
+            println!();
+
+            let hash = report.input_hash;
+            let answer = report.part(part).map(|report| &report.answer);
+            match (answer, hash) {
+                (Some(Result::Ok(answer)), Some(hash)) => (*answer, hash),
+                _ => bail!("Day {:02} part {} has no answer to submit", day, part),
+            }
+        }
+    };
+
+    if store.check(day, hash, part, answer) == AnswerStatus::Matches {
+        println!("Day {:02} part {}: {} is the accepted answer already, not submitting it", day, part, answer);
+        return Ok(true);
+    }
+    ledger.check(day, hash, part, answer)?;
+
+    let outcome = client.submit_answer(day, part, answer, SystemTime::now())?;
+    println!("Day {:02} part {}: {} is {}", day, part, answer, outcome);
+
+    match outcome {
+        Outcome::Correct => {
+            store.set(day, hash, part, answer);
+            store.save()?;
+            println!("Recorded it in {}", store.path().display());
+            Ok(true)
+        }
+        Outcome::Incorrect { hint } => {
+            let rejection = Rejection {
+                day,
+                input_hash: hash,
+                part,
+                answer,
+                hint,
+            };
+            ledger.add(rejection)?;
+            let bounds = ledger.bounds(day, hash, part);
+            if bounds != Default::default() {
+                println!("The answer is {}", bounds);
+            }
+            Ok(false)
+        }
+        Outcome::AlreadySolved => Ok(true),
+        Outcome::RateLimited { .. } => Ok(false),
+    }
+}
+
+fn main() -> Result<ExitCode> {
+    let mut args = env::args().skip(1);
+
+    let ok = match args.next().as_deref() {
+        Some("run") => run(args.collect())?,
+        Some("compare") => compare(args.collect())?,
+        Some("watch") => watch(args.collect())?,
+        Some("fetch") => fetch(args.collect())?,
+        Some("puzzle") => puzzle(args.collect())?,
+        Some("examples") => examples(args.collect())?,
+        Some("new") => new(args.collect())?,
+        Some("submit") => submit(args.collect())?,
+        _ => {
+            eprintln!("{}", USAGE);
+            return Ok(ExitCode::from(2));
+        }
+    };
+
+    Ok(if ok { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
@@ -0,0 +1,394 @@
+use anyhow::*;
+use regex::Regex;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::http::{Request, Response};
+use crate::solution::Part;
+
+/// Puzzle year everything in this crate is about.
+pub const YEAR: u16 = 2024;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Environment variable overriding the base URL, e.g. to point at a mock server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+/// Environment variable holding the session token.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable naming a file that holds the session token.
+pub const SESSION_FILE_VAR: &str = "AOC_SESSION_FILE";
+/// File the session token is read from when neither variable is set.
+pub const DEFAULT_SESSION_FILE: &str = ".aoc-session";
+
+/// Environment variable with contact details added to the User-Agent, as the
+/// site asks automated tools to identify their owner.
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC.
+const FIRST_UNLOCK: u64 = 1_733_029_200; // 2024-12-01 05:00:00 UTC
+
+/// When `day` becomes available.
+pub fn unlock_time(day: u8) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(FIRST_UNLOCK + (day as u64 - 1) * 24 * 60 * 60)
+}
+
+/// Fails with the remaining wait if `day` is not unlocked at `now`.
+pub fn ensure_unlocked(day: u8, now: SystemTime) -> Result<()> {
+    ensure!((1..=25).contains(&day), "there is no Day {:02}, expected 1 to 25", day);
+
+    if let Result::Ok(wait) = unlock_time(day).duration_since(now) {
+        let minutes = wait.as_secs().div_ceil(60);
+        bail!(
+            "Day {:02} is not unlocked yet, it unlocks on {}-12-{:02} at 05:00 UTC, in {}h {:02}m",
+            day,
+            YEAR,
+            day,
+            minutes / 60,
+            minutes % 60
+        );
+    }
+
+    Ok(())
+}
+
+/// User-Agent naming this crate and, if given, the `contact` of whoever runs it.
+pub fn user_agent(contact: Option<&str>) -> String {
+    match contact.map(str::trim).filter(|contact| !contact.is_empty()) {
+        Some(contact) => format!("{} (Advent of Code {} runner; {})", USER_AGENT, YEAR, contact),
+        None => format!("{} (Advent of Code {} runner)", USER_AGENT, YEAR),
+    }
+}
+
+/// Which way a wrong answer was off, when the site says so.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect { hint: Option<Hint> },
+    /// Submitted too soon after the previous answer, nothing was checked.
+    RateLimited { wait: Option<Duration> },
+    /// The part was solved before, nothing was checked.
+    AlreadySolved,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Incorrect { hint: Some(Hint::TooHigh) } => write!(f, "incorrect, too high"),
+            Outcome::Incorrect { hint: Some(Hint::TooLow) } => write!(f, "incorrect, too low"),
+            Outcome::Incorrect { hint: None } => write!(f, "incorrect"),
+            Outcome::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, wait {}s before submitting again", wait.as_secs())
+            }
+            Outcome::RateLimited { wait: None } => write!(f, "rate limited, wait before submitting again"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// The text of a page's `<article>`, or of the whole page if it has none,
+/// without tags and with whitespace collapsed.
+pub fn page_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Classifies the page the site answers a submission with.
+pub fn classify(html: &str) -> Result<Outcome> {
+    let text = page_text(html);
+
+    let outcome = if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("your answer is too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Outcome::Incorrect { hint }
+    } else if text.contains("You gave an answer too recently") {
+        let wait = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait")
+            .expect("valid regex")
+            .captures(&text)
+            .map(|caps| {
+                let minutes = caps.get(1).map_or(0, |minutes| minutes.as_str().parse::<u64>().unwrap_or(0));
+                let seconds = caps[2].parse::<u64>().unwrap_or(0);
+                Duration::from_secs(minutes * 60 + seconds)
+            });
+        Outcome::RateLimited { wait }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        bail!("unexpected response to the submission: {}", text);
+    };
+
+    Ok(outcome)
+}
+
+/// Client for the Advent of Code website.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Client {
+    base_url: String,
+    session: String,
+    user_agent: String,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            user_agent: user_agent(None),
+        }
+    }
+
+    /// Builds a client from the environment: the base URL is `base_url`,
+    /// `$AOC_BASE_URL` or the real site. The session token comes from
+    /// `$AOC_SESSION`, or from `session_file`, `$AOC_SESSION_FILE` or
+    /// `.aoc-session`, in that order.
+    pub fn from_env(base_url: Option<String>, session_file: Option<PathBuf>) -> Result<Self> {
+        let base_url = base_url
+            .or_else(|| env::var(BASE_URL_VAR).ok())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+
+        let session = match env::var(SESSION_VAR) {
+            Result::Ok(session) if session_file.is_none() => session,
+            _ => {
+                let path = session_file
+                    .or_else(|| env::var_os(SESSION_FILE_VAR).map(PathBuf::from))
+                    .unwrap_or_else(|| PathBuf::from(DEFAULT_SESSION_FILE));
+                fs::read_to_string(&path).with_context(|| {
+                    format!(
+                        "no session token, set {} or put the token from the site's session cookie in {}",
+                        SESSION_VAR,
+                        path.display()
+                    )
+                })?
+            }
+        };
+
+        let session = session.trim();
+        let session = session.strip_prefix("session=").unwrap_or(session);
+        ensure!(!session.is_empty(), "the session token is empty");
+
+        Ok(Self {
+            user_agent: user_agent(env::var(CONTACT_VAR).ok().as_deref()),
+            ..Self::new(base_url, session)
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// URL of the puzzle page of `day`.
+    pub fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    /// Sends a request with the session cookie and our User-Agent.
+    pub fn send(&self, request: Request) -> Result<Response> {
+        request
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", &self.user_agent)
+            .send()
+    }
+
+    /// Downloads the input of `day`, which must be unlocked at `now`.
+    pub fn download_input(&self, day: u8, now: SystemTime) -> Result<String> {
+        ensure_unlocked(day, now)?;
+
+        let url = format!("{}/input", self.day_url(day));
+        let response = self.send(Request::get(&url))?;
+        match response.status {
+            200 => Ok(response.body),
+            400 | 500 => bail!(
+                "the server rejected the request for {} ({}), is the session token still valid?",
+                url,
+                response.status
+            ),
+            404 => bail!("{} not found, is Day {:02} unlocked?", url, day),
+            status => bail!("downloading {} failed with status {}: {}", url, status, response.body.trim()),
+        }
+    }
+
+    /// Downloads the puzzle page of `day`, which must be unlocked at `now`.
+    /// Part two is only on it once part one is solved.
+    pub fn download_puzzle(&self, day: u8, now: SystemTime) -> Result<String> {
+        ensure_unlocked(day, now)?;
+
+        let url = self.day_url(day);
+        let response = self.send(Request::get(&url))?;
+        ensure!(
+            response.is_success(),
+            "downloading {} failed with status {}",
+            url,
+            response.status
+        );
+        Ok(response.body)
+    }
+
+    /// Submits `answer` for `part` of `day`, which must be unlocked at `now`.
+    pub fn submit_answer(&self, day: u8, part: Part, answer: usize, now: SystemTime) -> Result<Outcome> {
+        ensure_unlocked(day, now)?;
+
+        let url = format!("{}/answer", self.day_url(day));
+        let (level, answer) = (part.to_string(), answer.to_string());
+        let response = self.send(Request::post_form(&url, &[("level", &level), ("answer", &answer)]))?;
+        match response.status {
+            200 => classify(&response.body),
+            400 | 500 => bail!(
+                "the server rejected the submission to {} ({}), is the session token still valid?",
+                url,
+                response.status
+            ),
+            status => bail!("submitting to {} failed with status {}: {}", url, status, page_text(&response.body)),
+        }
+    }
+
+    /// Makes sure the input of `day` is at `path`, downloading it only if it
+    /// is not there yet. Returns whether it was downloaded.
+    pub fn fetch_input(&self, day: u8, path: &Path, now: SystemTime) -> Result<bool> {
+        if path.exists() {
+            return Ok(false);
+        }
+
+        let input = self.download_input(day, now)?;
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        fs::write(path, input).with_context(|| format!("failed to write {}", path.display()))?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::serve_once;
+    use crate::testing::ScratchDir;
+
+    fn during_event(day: u8) -> SystemTime {
+        unlock_time(day) + Duration::from_secs(60)
+    }
+
+    #[test]
+    fn knows_when_days_unlock() {
+        assert!(ensure_unlocked(1, during_event(1)).is_ok());
+        assert!(ensure_unlocked(25, SystemTime::now()).is_ok());
+
+        let err = ensure_unlocked(6, during_event(5)).unwrap_err();
+        assert_eq!(
+            "Day 06 is not unlocked yet, it unlocks on 2024-12-06 at 05:00 UTC, in 23h 59m",
+            err.to_string()
+        );
+        assert!(ensure_unlocked(26, SystemTime::now()).is_err());
+    }
+
+    #[test]
+    fn fetches_and_caches_inputs() {
+        let dir = ScratchDir::new("fetch");
+        let path = dir.join("input/05.txt");
+
+        let (url, server) = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n47|53\n");
+        let client = Client::new(format!("{}/", url), "abc123");
+
+        assert!(client.fetch_input(5, &path, during_event(5)).unwrap());
+        assert_eq!("47|53\n", fs::read_to_string(&path).unwrap());
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/5/input HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=abc123\r\n"));
+        assert!(request.contains("\r\nUser-Agent: adv-code-2024/0.1.0 (Advent of Code 2024 runner)\r\n"));
+
+        // cached now, the server is gone so any request would fail
+        assert!(!client.fetch_input(5, &path, during_event(5)).unwrap());
+    }
+
+    #[test]
+    fn classifies_submissions() {
+        let page = |text: &str| format!("<html><main><article><p>{}</p></article></main></html>", text);
+
+        let correct = "That's the right answer! You are <em>one gold star</em> closer to finding the Chief Historian.";
+        assert_eq!(Outcome::Correct, classify(&page(correct)).unwrap());
+
+        let too_high = "That's not the right answer; your answer is too high. \
+                        Please wait one minute before trying again.";
+        assert_eq!(Outcome::Incorrect { hint: Some(Hint::TooHigh) }, classify(&page(too_high)).unwrap());
+        let too_low = "That's not the right answer; your answer is too low.";
+        assert_eq!(Outcome::Incorrect { hint: Some(Hint::TooLow) }, classify(&page(too_low)).unwrap());
+        let wrong = "That's not the right answer. If you're stuck, make sure you're using the full input data.";
+        assert_eq!(Outcome::Incorrect { hint: None }, classify(&page(wrong)).unwrap());
+
+        let recently = "You gave an answer too recently; you have to wait after submitting an answer before \
+                        trying again. You have 1m 5s left to wait.";
+        let wait = Some(Duration::from_secs(65));
+        assert_eq!(Outcome::RateLimited { wait }, classify(&page(recently)).unwrap());
+        let recently = "You gave an answer too recently; you have to wait. You have 34s left to wait.";
+        let wait = Some(Duration::from_secs(34));
+        assert_eq!(Outcome::RateLimited { wait }, classify(&page(recently)).unwrap());
+
+        let solved = "You don't seem to be solving the right level. Did you already complete it?";
+        assert_eq!(Outcome::AlreadySolved, classify(&page(solved)).unwrap());
+
+        assert!(classify(&page("Something else")).is_err());
+    }
+
+    #[test]
+    fn submits_answers() {
+        let body = "<article><p>That's the\n  right answer!</p></article>";
+        let (url, server) = serve_once(&format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}", body.len(), body));
+        let client = Client::new(url, "abc123");
+
+        assert_eq!(Outcome::Correct, client.submit_answer(5, Part::Two, 123, during_event(5)).unwrap());
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/5/answer HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=abc123\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=123"));
+
+        assert!(client.submit_answer(6, Part::One, 1, during_event(5)).is_err());
+    }
+
+    #[test]
+    fn refuses_locked_days_and_reports_errors() {
+        let dir = ScratchDir::new("fetch");
+        let path = dir.join("06.txt");
+        let client = Client::new("http://127.0.0.1:9", "abc123");
+        assert!(client.fetch_input(6, &path, during_event(5)).is_err());
+        assert!(!path.exists());
+
+        let (url, _) = serve_once("HTTP/1.1 400 Bad Request\r\n\r\nPuzzle inputs differ by user.");
+        let err = Client::new(url, "expired").download_input(5, during_event(5)).unwrap_err();
+        assert!(err.to_string().contains("session token"));
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::*;
+use crate::{Parser, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+
+    /// The left and right location lists.
+    type Input = (Vec<usize>, Vec<usize>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        let parser = Parser::new(Self::DAY, input);
+        for line in parser.lines() {
+            let mut iter = line.split_whitespace();
+            left.push(parser.number(parser.expect(iter.next(), line, "two numbers")?)?);
+            right.push(parser.number(parser.expect(iter.next(), line, "two numbers")?)?);
+        }
+
+        Ok((left, right))
+    }
+
+    fn part1((left, right): &Self::Input) -> Result<usize> {
+        let mut left = left.clone();
+        let mut right = right.clone();
+
+        // sort vectors in ascending order
+        left.sort();
+        right.sort();
+
+        // let mut sum = 0;
+        // for i in 0..left.len() {
+        //     let larger = left[i].max(right[i]);
+        //     let smaller = left[i].min(right[i]);
+        //     sum += larger - smaller;
+        // }
+
+        let sum = (0..left.len()).map(|i| left[i].max(right[i]) - left[i].min(right[i])).sum();
+
+        Ok(sum)
+    }
+
+    fn part2((left, right): &Self::Input) -> Result<usize> {
+        let char_count: usize = left.iter().map(|l| l * right.iter().filter(|&r| l == r).count()).sum();
+
+        Ok(char_count)
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::*;
+use itertools::Itertools;
+use crate::{ParseError, Parser, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+
+    /// One report of levels per line.
+    type Input = Vec<Vec<usize>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let parser = Parser::new(Self::DAY, input);
+        let reports = parser
+            .lines()
+            .map(|line| line.split_whitespace().map(|c| parser.number(c)).collect())
+            .collect::<Result<_, ParseError>>()?;
+
+        Ok(reports)
+    }
+
+    fn part1(reports: &Self::Input) -> Result<usize> {
+        let mut num_of_safe = 0;
+        for res in reports {
+            res.iter().tuple_windows().all(|(a, b)| (a < b) && (b - a) <= 3).then(|| num_of_safe += 1);
+            res.iter().tuple_windows().all(|(a, b)| (a > b) && (a - b) <= 3).then(|| num_of_safe += 1);
+        }
+
+        Ok(num_of_safe)
+    }
+
+    fn part2(reports: &Self::Input) -> Result<usize> {
+        let mut num_of_safe = 0;
+        for report in reports {
+            let backup = num_of_safe;
+            let mut res = report.clone();
+            res.iter().tuple_windows().all(|(a, b)| (a < b) && (b - a) <= 3).then(|| num_of_safe += 1);
+            res.iter().tuple_windows().all(|(a, b)| (a > b) && (a - b) <= 3).then(|| num_of_safe += 1);
+
+            if backup != num_of_safe { continue }
+
+            let res_backup = res.clone();
+            let mut found: bool = false;
+            for i in 0..res.len() {
+                res.remove(i);
+
+                res.iter().tuple_windows().all(|(a, b)| (a < b) && (b - a) <= 3).then(|| { num_of_safe += 1; found = true; });
+                res.iter().tuple_windows().all(|(a, b)| (a > b) && (a - b) <= 3).then(|| { num_of_safe += 1; found = true; });
+
+                if found { break } else { res = res_backup.clone(); }
+            }
+        }
+
+        Ok(num_of_safe)
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::*;
+use regex::Regex;
+use crate::{Parser, Solution};
+
+pub struct Day03;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Do,
+    Dont,
+    Mul(u32, u32),
+}
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+
+    /// The valid instructions found in the corrupted memory, in order.
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let combined_regex = Regex::new(
+            r"(?P<do>do\(\))|(?P<dont>don't\(\))|(?P<mul>mul\((\d+),(\d+)\))"
+        )?;
+
+        let parser = Parser::new(Self::DAY, input);
+        let mut instructions = Vec::new();
+        for line in parser.lines() {
+            for caps in combined_regex.captures_iter(line) {
+                if caps.name("do").is_some() {
+                    instructions.push(Instruction::Do);
+                } else if caps.name("dont").is_some() {
+                    instructions.push(Instruction::Dont);
+                } else if caps.name("mul").is_some() {
+                    let n: u32 = parser.number(caps.get(4).unwrap().as_str())?;
+                    let m: u32 = parser.number(caps.get(5).unwrap().as_str())?;
+                    instructions.push(Instruction::Mul(n, m));
+                }
+            }
+        }
+
+        Ok(instructions)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<usize> {
+        let total: u32 = instructions
+            .iter()
+            .map(|instruction| match instruction {
+                Instruction::Mul(n, m) => n * m,
+                _ => 0,
+            })
+            .sum();
+
+        Ok(total as usize)
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<usize> {
+        let mut pairs = Vec::new();
+        let mut enabled: bool = true;
+        for instruction in instructions {
+            match *instruction {
+                Instruction::Do => enabled = true,
+                Instruction::Dont => enabled = false,
+                Instruction::Mul(n, m) if enabled => pairs.push((n, m)),
+                Instruction::Mul(..) => {}
+            }
+        }
+
+        let total: u32 = pairs.iter().map(|(n, m)| n * m).sum();
+        Ok(total as usize)
+    }
+}
@@ -0,0 +1,88 @@
+use anyhow::*;
+use crate::{Dir8, Grid, Parser, Point, Solution};
+
+pub struct Day04;
+
+fn get_char(grid: &Grid<char>, pos: Point) -> Option<char> {
+    grid.get_point(pos).copied()
+}
+
+fn is_xmas(grid: &Grid<char>, pos: Point) -> bool {
+    let diagonals = [
+        [Dir8::NW, Dir8::SE], // tl br
+        [Dir8::NE, Dir8::SW], // tr bl
+    ];
+
+    for diag in &diagonals {
+        // one way
+        if !matches_pattern(grid, pos, diag[0], diag[1]) {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn matches_pattern(grid: &Grid<char>, pos: Point, dir1: Dir8, dir2: Dir8) -> bool {
+    let (dir1, dir2) = (dir1.offset(), dir2.offset());
+
+    if let Some('M') = get_char(grid, pos + dir1) {
+        if let Some('S') = get_char(grid, pos + dir2) {
+            return true;
+        }
+    }
+
+    // reverse
+    if let Some('S') = get_char(grid, pos + dir1) {
+        if let Some('M') = get_char(grid, pos + dir2) {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn find_all(grid: &Grid<char>, c: char) -> impl Iterator<Item = Point> + '_ {
+    grid.positions_where(move |&tile| tile == c)
+        .filter_map(Point::from_index)
+}
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Parser::new(Self::DAY, input).grid()?)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize> {
+        let mut total: usize = 0;
+
+        // each X
+        for pos in find_all(grid, 'X') {
+            // check around
+            for dir in Dir8::all().map(Dir8::offset) {
+                // check for M A S
+                if let Some('M') = get_char(grid, pos + dir) {
+                    if let Some('A') = get_char(grid, pos + dir * 2) {
+                        if let Some('S') = get_char(grid, pos + dir * 3) {
+                            total += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(total)
+    }
+
+    fn part2(grid: &Self::Input) -> Result<usize> {
+        let total = find_all(grid, 'A')
+            .filter(|&pos| is_xmas(grid, pos))
+            .count();
+
+        Ok(total)
+    }
+}
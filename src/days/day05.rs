@@ -0,0 +1,113 @@
+use anyhow::*;
+use std::collections::HashSet;
+use petgraph::graphmap::DiGraphMap;
+use crate::{Parser, Solution};
+
+pub struct Day05;
+
+fn is_valid_update(update: &[i32], rules: &[(i32, i32)]) -> bool {
+    let page_positions = update
+        .iter()
+        .enumerate()
+        .map(|(index, &page)| (page, index))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    for &(x, y) in rules {
+        if let (Some(&pos_x), Some(&pos_y)) = (page_positions.get(&x), page_positions.get(&y)) {
+            if pos_x > pos_y {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+fn find_middle_page(update: &[i32]) -> i32 {
+    update[update.len() / 2]
+}
+
+fn parse_input(parser: &Parser) -> Result<<Day05 as Solution>::Input> {
+    let input = parser.input().trim();
+    let (rules_section, updates_section) = input.split_once("\n\n").ok_or_else(|| {
+        parser.error_at_end(input, "expected a blank line between the rules and the updates")
+    })?;
+
+    let rules = rules_section
+        .lines()
+        .map(|line| {
+            let (x, y) = parser.split_once(line, "|")?;
+            Ok((parser.number(x)?, parser.number(y)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let updates = updates_section
+        .lines()
+        .map(|line| {
+            line.split(',')
+                .map(|num| parser.number(num))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((rules, updates))
+}
+
+/// Orders the pages of `update` by the rules, failing if they contradict each other.
+fn fix_update(update: &[i32], rules: &[(i32, i32)]) -> Result<Vec<i32>> {
+    let update_pages: HashSet<i32> = update.iter().copied().collect();
+    let mut graph = DiGraphMap::new();
+
+    for &page in &update_pages {
+        graph.add_node(page);
+    }
+
+    for &(x, y) in rules {
+        if update_pages.contains(&x) && update_pages.contains(&y) {
+            graph.add_edge(x, y, ());
+        }
+    }
+
+    let mut sorted_pages = petgraph::algo::toposort(&graph, None)
+        .map_err(|cycle| anyhow!("the rules for update {:?} contain a cycle through page {}", update, cycle.node_id()))?
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    sorted_pages.retain(|page| update.contains(page));
+    Ok(sorted_pages)
+}
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+
+    /// The page ordering rules and the updates to check against them.
+    type Input = (Vec<(i32, i32)>, Vec<Vec<i32>>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(&Parser::new(Self::DAY, input))
+    }
+
+    fn part1((rules, updates): &Self::Input) -> Result<usize> {
+        let res: i32 = updates
+            .iter()
+            .filter(|update| is_valid_update(update, rules))
+            .map(|update| find_middle_page(update))
+            .sum();
+
+        Ok(res as usize)
+    }
+
+    fn part2((rules, updates): &Self::Input) -> Result<usize> {
+        let res: i32 = updates
+            .iter()
+            .filter(|update| !is_valid_update(update, rules))
+            .map(|update| {
+                let fixed_update = fix_update(update, rules)?;
+                Ok(find_middle_page(&fixed_update))
+            })
+            .sum::<Result<_>>()?;
+
+        Ok(res as usize)
+    }
+}
@@ -0,0 +1,95 @@
+use anyhow::*;
+use std::collections::HashSet;
+use crate::{Direction, Grid, Parser, Point, Solution};
+
+pub struct Day06;
+
+fn get_next_pos(grid: &Grid<char>, guard: Point, direction: &mut Direction) -> Option<Point> {
+    let next = guard + direction.offset();
+    let char = grid.get_point(next)?;
+
+    if char == &'#' {
+        *direction = direction.turn_right();
+        return Some(guard);
+    }
+
+    Some(next)
+}
+
+fn gets_in_loop(grid: &Grid<char>, start: Point, start_direction: Direction) -> bool {
+    let mut visited_obstacles: Vec<(Point, Direction)> = Vec::new();
+
+    let mut direction = start_direction;
+    let mut guard = start;
+
+    while let Some(next) = get_next_pos(grid, guard, &mut direction) {
+        if guard == next {
+            if visited_obstacles.contains(&(guard, direction)) {
+                return true;
+            }
+
+            visited_obstacles.push((guard, direction));
+        }
+
+        guard = next;
+    }
+
+    false
+}
+
+fn find_guard(grid: &Grid<char>) -> Result<Point> {
+    grid.find(&'^')
+        .and_then(Point::from_index)
+        .context("guard not found in grid")
+}
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Parser::new(Self::DAY, input).grid()?)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize> {
+        let mut guard = find_guard(grid)?;
+        let mut direction = Direction::Up; // UP
+
+        let mut visited = HashSet::new();
+        visited.insert(guard);
+
+        while let Some(next) = get_next_pos(grid, guard, &mut direction) {
+            guard = next;
+            visited.insert(guard);
+        }
+
+        Ok(visited.len())
+    }
+
+    fn part2(grid: &Self::Input) -> Result<usize> {
+        let mut grid = grid.clone();
+        let mut guard = find_guard(&grid)?;
+        let mut direction = Direction::Up;
+
+        let mut visited = HashSet::new();
+        let mut count = 0;
+
+        while let Some(next) = get_next_pos(&grid, guard, &mut direction) {
+            visited.insert(guard);
+
+            if !visited.contains(&next) {
+                grid[next] = '#';
+                if gets_in_loop(&grid, guard, direction) {
+                    count += 1;
+                }
+                grid[next] = '.';
+            }
+
+            guard = next;
+        }
+
+        Ok(count)
+    }
+}
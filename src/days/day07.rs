@@ -0,0 +1,95 @@
+use anyhow::*;
+use crate::{Parser, Solution};
+
+pub struct Day07;
+
+fn reachable(start: usize, target: usize, num: &[usize]) -> bool {
+    if num.is_empty() {
+        return start == target;
+    }
+
+    if start > target {
+        return false;
+    }
+
+    let (head, rest) = num.split_first().unwrap();
+    reachable(start * head, target, rest) || reachable(start + head, target, rest)
+}
+
+fn reachable_concat(start: usize, target: usize, num: &[usize]) -> bool {
+    if num.is_empty() {
+        return start == target;
+    }
+
+    if start > target {
+        return false;
+    }
+
+    let (head, rest) = num.split_first().unwrap();
+    reachable_concat(start * head, target, rest)
+        || reachable_concat(start + head, target, rest)
+        || reachable_concat(concat(start, *head), target, rest)
+}
+
+fn concat(a: usize, b: usize) -> usize {
+    let mut off = 1;
+
+    while off <= b {
+        off *= 10;
+    }
+
+    a * off + b
+}
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Bridge Repair";
+
+    /// Each equation's test value together with its numbers.
+    type Input = Vec<(usize, Vec<usize>)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut equations: Vec<(usize, Vec<usize>)> = Vec::new();
+        let parser = Parser::new(Self::DAY, input);
+        for line in parser.lines() {
+            let (result, numbers) = parser.split_once(line, ":")?;
+            let result = parser.number(result)?;
+            let numbers = numbers
+                .split_whitespace()
+                .map(|n| parser.number(n))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if numbers.is_empty() {
+                return Err(parser.error_at_end(line, "expected at least one number").into());
+            }
+
+            equations.push((result, numbers));
+        }
+
+        Ok(equations)
+    }
+
+    fn part1(equations: &Self::Input) -> Result<usize> {
+        let mut res = 0;
+        for (target, numbers) in equations {
+            let (start, numbers) = numbers.split_first().unwrap();
+            if reachable(*start, *target, numbers) {
+                res += target;
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn part2(equations: &Self::Input) -> Result<usize> {
+        let mut res = 0;
+        for (target, numbers) in equations {
+            let (start, numbers) = numbers.split_first().unwrap();
+            if reachable_concat(*start, *target, numbers) {
+                res += target;
+            }
+        }
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::*;
+use std::collections::{HashMap, HashSet};
+use itertools::Itertools;
+use crate::{Grid, Parser, Point, Solution};
+
+pub struct Day08;
+
+fn find_antennas(grid: &Grid<char>) -> HashMap<char, Vec<Point>> {
+    let mut antennas: HashMap<char, Vec<Point>> = HashMap::new();
+
+    for ((row, col), &c) in grid.iter() {
+        if c == '.' {
+            continue;
+        }
+
+        let pos = Point::from_index((row, col)).expect("grid index fits in i64");
+        antennas.entry(c).or_default().push(pos);
+    }
+
+    antennas
+}
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Parser::new(Self::DAY, input).grid()?)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize> {
+        let antennas = find_antennas(grid);
+
+        let mut anti: HashSet<Point> = HashSet::new();
+        for positions in antennas.values() {
+            for pair in positions.iter().combinations(2) {
+                let (a, b) = (*pair[0], *pair[1]);
+                let delta = b - a;
+
+                let anti_1 = b + delta;
+                let anti_2 = a - delta;
+
+                if grid.contains(anti_1) {
+                    anti.insert(anti_1);
+                }
+
+                if grid.contains(anti_2) {
+                    anti.insert(anti_2);
+                }
+            }
+        }
+
+        Ok(anti.len())
+    }
+
+    fn part2(grid: &Self::Input) -> Result<usize> {
+        let antennas = find_antennas(grid);
+
+        let mut anti: HashSet<Point> = HashSet::new();
+        for positions in antennas.values() {
+            for pair in positions.iter().combinations(2) {
+                let (a, b) = (*pair[0], *pair[1]);
+                let delta = b - a;
+
+                let mut antinode = b;
+                while grid.contains(antinode) {
+                    anti.insert(antinode);
+                    antinode += delta;
+                }
+
+                let mut antinode = a;
+                while grid.contains(antinode) {
+                    anti.insert(antinode);
+                    antinode -= delta;
+                }
+            }
+        }
+
+        Ok(anti.len())
+    }
+}
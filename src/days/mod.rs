@@ -0,0 +1,105 @@
+use crate::solution::{DynSolution, Registered};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+
+/// Every implemented day, in order.
+pub static DAYS: &[&dyn DynSolution] = &[
+    &Registered::<day01::Day01>::new(),
+    &Registered::<day02::Day02>::new(),
+    &Registered::<day03::Day03>::new(),
+    &Registered::<day04::Day04>::new(),
+    &Registered::<day05::Day05>::new(),
+    &Registered::<day06::Day06>::new(),
+    &Registered::<day07::Day07>::new(),
+    &Registered::<day08::Day08>::new(),
+];
+
+pub fn get(day: u8) -> Option<&'static dyn DynSolution> {
+    DAYS.iter().copied().find(|solution| solution.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use super::*;
+    use crate::examples;
+
+    fn assert_examples(day: u8) {
+        let solution = get(day).expect("day is registered");
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(examples::EXAMPLES_DIR);
+
+        let failures = examples::check_day(solution, dir)
+            .unwrap()
+            .into_iter()
+            .filter(|outcome| !outcome.passed())
+            .map(|outcome| {
+                format!(
+                    "example {} part {}: expected {}, got {:?}",
+                    outcome.name, outcome.part, outcome.expected, outcome.actual
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert!(failures.is_empty(), "Day {:02}:\n{}", day, failures.join("\n"));
+    }
+
+    #[test]
+    fn registry_is_ordered() {
+        assert!(DAYS.windows(2).all(|pair| pair[0].day() < pair[1].day()));
+    }
+
+    #[test]
+    fn day05_reports_cyclic_rules() {
+        let solution = get(5).unwrap();
+        let input = solution.parse("1|2\n2|3\n3|1\n\n3,2,1\n").unwrap();
+        let err = solution.solve(&input, crate::Part::Two).unwrap_err();
+        assert!(err.to_string().starts_with("the rules for update [3, 2, 1] contain a cycle through page "));
+    }
+
+    #[test]
+    fn day01() {
+        assert_examples(1);
+    }
+
+    #[test]
+    fn day02() {
+        assert_examples(2);
+    }
+
+    #[test]
+    fn day03() {
+        assert_examples(3);
+    }
+
+    #[test]
+    fn day04() {
+        assert_examples(4);
+    }
+
+    #[test]
+    fn day05() {
+        assert_examples(5);
+    }
+
+    #[test]
+    fn day06() {
+        assert_examples(6);
+    }
+
+    #[test]
+    fn day07() {
+        assert_examples(7);
+    }
+
+    #[test]
+    fn day08() {
+        assert_examples(8);
+    }
+}
@@ -0,0 +1,285 @@
+use anyhow::{anyhow, Error, Result};
+use std::fmt;
+use std::str::FromStr;
+use crate::point::Point;
+
+/// One of the four orthogonal directions on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions in clockwise order, starting with `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    /// Unit step in this direction, in `(row, col)` order.
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(-1, 0),
+            Direction::Right => Point::new(0, 1),
+            Direction::Down => Point::new(1, 0),
+            Direction::Left => Point::new(0, -1),
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = Error;
+
+    /// Accepts arrows (`^ > v <`), compass points (`N E S W`) and `U R D L`.
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            '^' | 'N' | 'U' => Ok(Direction::Up),
+            '>' | 'E' | 'R' => Ok(Direction::Right),
+            'v' | 'S' | 'D' => Ok(Direction::Down),
+            '<' | 'W' | 'L' => Ok(Direction::Left),
+            _ => Err(anyhow!("invalid direction '{}'", c)),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(anyhow!("invalid direction '{}'", s)),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        };
+
+        write!(f, "{}", arrow)
+    }
+}
+
+/// One of the eight compass directions, including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// All directions in clockwise order, starting with `N`.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    pub fn all() -> impl Iterator<Item = Dir8> {
+        Self::ALL.into_iter()
+    }
+
+    pub fn orthogonal() -> impl Iterator<Item = Dir8> {
+        Self::all().filter(|dir| !dir.is_diagonal())
+    }
+
+    pub fn diagonal() -> impl Iterator<Item = Dir8> {
+        Self::all().filter(|dir| dir.is_diagonal())
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        matches!(self, Dir8::NE | Dir8::SE | Dir8::SW | Dir8::NW)
+    }
+
+    /// Rotates 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Rotates 45 degrees counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// Unit step in this direction, in `(row, col)` order.
+    pub fn offset(self) -> Point {
+        match self {
+            Dir8::N => Point::new(-1, 0),
+            Dir8::NE => Point::new(-1, 1),
+            Dir8::E => Point::new(0, 1),
+            Dir8::SE => Point::new(1, 1),
+            Dir8::S => Point::new(1, 0),
+            Dir8::SW => Point::new(1, -1),
+            Dir8::W => Point::new(0, -1),
+            Dir8::NW => Point::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Dir8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Dir8::N,
+            Direction::Right => Dir8::E,
+            Direction::Down => Dir8::S,
+            Direction::Left => Dir8::W,
+        }
+    }
+}
+
+impl From<Dir8> for Point {
+    fn from(direction: Dir8) -> Self {
+        direction.offset()
+    }
+}
+
+impl FromStr for Dir8 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "N" => Ok(Dir8::N),
+            "NE" => Ok(Dir8::NE),
+            "E" => Ok(Dir8::E),
+            "SE" => Ok(Dir8::SE),
+            "S" => Ok(Dir8::S),
+            "SW" => Ok(Dir8::SW),
+            "W" => Ok(Dir8::W),
+            "NW" => Ok(Dir8::NW),
+            _ => s.parse::<Direction>().map(Dir8::from),
+        }
+    }
+}
+
+impl Point {
+    /// The four orthogonal neighbours, clockwise from above.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |dir| self + dir.offset())
+    }
+
+    /// All eight neighbours, clockwise from above.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Dir8::all().map(move |dir| self + dir.offset())
+    }
+
+    /// The four diagonal neighbours, clockwise from the top right.
+    pub fn diagonal_neighbours(self) -> impl Iterator<Item = Point> {
+        Dir8::diagonal().map(move |dir| self + dir.offset())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir, dir.turn_right().turn_left());
+            assert_eq!(dir.reverse(), dir.turn_right().turn_right());
+            assert_eq!(Point::ORIGIN, dir.offset() + dir.reverse().offset());
+        }
+
+        for dir in Dir8::ALL {
+            assert_eq!(dir, dir.turn_right().turn_left());
+            assert_eq!(Point::ORIGIN, dir.offset() + dir.reverse().offset());
+        }
+
+        assert_eq!(Dir8::NW, Dir8::N.turn_left());
+        assert_eq!(Dir8::SW, Dir8::NE.reverse());
+    }
+
+    #[test]
+    fn parses() {
+        for (s, dir) in [("^", Direction::Up), ("E", Direction::Right), ("v", Direction::Down), ("L", Direction::Left)] {
+            assert_eq!(dir, s.parse().unwrap());
+        }
+
+        assert_eq!(Direction::Left, Direction::try_from('<').unwrap());
+        assert!("x".parse::<Direction>().is_err());
+        assert!("^^".parse::<Direction>().is_err());
+        assert_eq!(Dir8::SE, "SE".parse().unwrap());
+        assert_eq!(Dir8::N, "^".parse().unwrap());
+        assert_eq!("^>v<", Direction::ALL.map(|d| d.to_string()).concat());
+    }
+
+    #[test]
+    fn neighbourhoods() {
+        assert_eq!(4, Dir8::orthogonal().count());
+        assert_eq!(4, Dir8::diagonal().count());
+        assert!(Dir8::orthogonal().map(Dir8::offset).eq(Direction::ALL.map(Direction::offset)));
+
+        let p = Point::new(5, 5);
+        assert!(p.neighbours().all(|n| p.manhattan(n) == 1));
+        assert!(p.diagonal_neighbours().all(|n| p.manhattan(n) == 2));
+        assert_eq!(8, p.neighbours8().filter(|&n| p.chebyshev(n) == 1).count());
+    }
+}
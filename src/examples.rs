@@ -0,0 +1,241 @@
+use anyhow::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::input::normalize;
+use crate::solution::{DynSolution, Part};
+
+/// Directory holding the puzzle examples, relative to the crate root.
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// A puzzle example: `NN-<name>.txt` holds the input and the optional
+/// `NN-<name>.expected` sidecar lists the expected answers, one `partN = <answer>`
+/// per line. Parts without an expected answer are not checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u8,
+    pub name: String,
+    pub path: PathBuf,
+    pub input: String,
+    pub expected: BTreeMap<Part, usize>,
+}
+
+/// Result of solving one part of an example.
+#[derive(Debug)]
+pub struct ExampleOutcome {
+    pub name: String,
+    pub part: Part,
+    pub expected: usize,
+    pub actual: Result<usize>,
+}
+
+impl ExampleOutcome {
+    pub fn passed(&self) -> bool {
+        matches!(self.actual, Result::Ok(actual) if actual == self.expected)
+    }
+}
+
+/// Path of the input file for the example `name` of `day`.
+pub fn example_path<P: AsRef<Path>>(dir: P, day: u8, name: &str) -> PathBuf {
+    dir.as_ref().join(format!("{:02}-{}.txt", day, name))
+}
+
+/// Path of the expected-answers sidecar next to an example input.
+pub fn expected_path(example: &Path) -> PathBuf {
+    example.with_extension("expected")
+}
+
+/// Parses the `partN = <answer>` lines of an expected-answers file.
+pub fn parse_expected(text: &str) -> Result<BTreeMap<Part, usize>> {
+    let mut expected = BTreeMap::new();
+
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (part, answer) = line
+            .split_once('=')
+            .and_then(|(part, answer)| {
+                let part = part.trim().strip_prefix("part")?.parse::<Part>().ok()?;
+                let answer = answer.trim().parse::<usize>().ok()?;
+                Some((part, answer))
+            })
+            .with_context(|| format!("line {}: expected partN = <answer>, found {}", idx + 1, line))?;
+
+        expected.insert(part, answer);
+    }
+
+    Ok(expected)
+}
+
+/// Renders expected answers in the format read by [`parse_expected`].
+pub fn format_expected(expected: &BTreeMap<Part, usize>) -> String {
+    expected
+        .iter()
+        .map(|(part, answer)| format!("part{} = {}\n", part, answer))
+        .collect()
+}
+
+/// Loads every example of `day` from `dir`, sorted by name.
+pub fn load<P: AsRef<Path>>(dir: P, day: u8) -> Result<Vec<Example>> {
+    let dir = dir.as_ref();
+    let prefix = format!("{:02}-", day);
+
+    let entries = match fs::read_dir(dir) {
+        Result::Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("failed to read {}", dir.display())),
+    };
+
+    let mut examples = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(name) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(&prefix))
+            .and_then(|name| name.strip_suffix(".txt"))
+        else {
+            continue;
+        };
+
+        let input = fs::read_to_string(&path)
+            .map(|input| normalize(&input))
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        let expected_file = expected_path(&path);
+        let expected = if expected_file.exists() {
+            let text = fs::read_to_string(&expected_file)
+                .with_context(|| format!("failed to read {}", expected_file.display()))?;
+            parse_expected(&text).with_context(|| format!("invalid {}", expected_file.display()))?
+        } else {
+            BTreeMap::new()
+        };
+
+        examples.push(Example {
+            day,
+            name: name.to_string(),
+            path,
+            input,
+            expected,
+        });
+    }
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+/// Writes a new example of `day` to `dir`, named after the first free number
+/// or replacing the empty placeholder of a new day, with the expected answers
+/// next to it unless there are none. Returns the path of the input.
+pub fn save<P: AsRef<Path>>(dir: P, day: u8, input: &str, expected: &BTreeMap<Part, usize>) -> Result<PathBuf> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+
+    let path = (1..)
+        .map(|idx| example_path(dir, day, &idx.to_string()))
+        .find(|path| fs::metadata(path).map_or(true, |meta| meta.len() == 0))
+        .expect("a free example name");
+    fs::write(&path, input).with_context(|| format!("failed to write {}", path.display()))?;
+
+    if !expected.is_empty() {
+        let expected_file = expected_path(&path);
+        fs::write(&expected_file, format_expected(expected))
+            .with_context(|| format!("failed to write {}", expected_file.display()))?;
+    }
+
+    Ok(path)
+}
+
+/// Solves every part of the example that has an expected answer.
+pub fn check(solution: &dyn DynSolution, example: &Example) -> Vec<ExampleOutcome> {
+    let input = solution.parse(&example.input);
+
+    example
+        .expected
+        .iter()
+        .map(|(&part, &expected)| ExampleOutcome {
+            name: example.name.clone(),
+            part,
+            expected,
+            actual: match &input {
+                Result::Ok(input) => solution.solve(input, part),
+                Err(err) => Err(anyhow!("failed to parse example: {:#}", err)),
+            },
+        })
+        .collect()
+}
+
+/// Checks all examples of a day, failing with a description of every mismatch.
+/// Fails if the day has no examples or none of them has an expected answer,
+/// as a freshly scaffolded day does, so that its test does not pass unchecked.
+pub fn check_day<P: AsRef<Path>>(solution: &dyn DynSolution, dir: P) -> Result<Vec<ExampleOutcome>> {
+    let dir = dir.as_ref();
+    let examples = load(dir, solution.day())?;
+    ensure!(!examples.is_empty(), "Day {:02} has no examples", solution.day());
+    ensure!(
+        examples.iter().any(|example| !example.expected.is_empty()),
+        "Day {:02} has no expected answers, fill them in {}",
+        solution.day(),
+        dir.join(format!("{:02}-*.expected", solution.day())).display()
+    );
+
+    Ok(examples
+        .iter()
+        .flat_map(|example| check(solution, example))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ScratchDir;
+
+    #[test]
+    fn parses_expected_answers() {
+        let expected = parse_expected("part1 = 161\n# only part 1\n").unwrap();
+        assert_eq!(Some(&161), expected.get(&Part::One));
+        assert_eq!(None, expected.get(&Part::Two));
+        assert_eq!("part1 = 161\n", format_expected(&expected));
+
+        assert!(parse_expected("part3 = 1").is_err());
+        assert!(parse_expected("part1: 1").is_err());
+    }
+
+    #[test]
+    fn saves_examples() {
+        let scratch = ScratchDir::new("examples");
+        let dir = scratch.path();
+
+        let expected = BTreeMap::from([(Part::One, 11)]);
+        assert_eq!(dir.join("01-1.txt"), save(dir, 1, "3   4\n", &expected).unwrap());
+        assert_eq!(dir.join("01-2.txt"), save(dir, 1, "1\n", &BTreeMap::new()).unwrap());
+        fs::write(dir.join("02-1.txt"), "").unwrap();
+        assert_eq!(dir.join("02-1.txt"), save(dir, 2, "2\n", &expected).unwrap());
+
+        let examples = load(dir, 1).unwrap();
+        assert_eq!(2, examples.len());
+        assert_eq!(expected, examples[0].expected);
+        assert!(examples[1].expected.is_empty());
+        assert!(!dir.join("01-2.expected").exists());
+    }
+
+    #[test]
+    fn requires_expected_answers() {
+        let dir = ScratchDir::new("examples");
+        let solution = crate::days::get(1).unwrap();
+        assert!(check_day(solution, dir.path()).is_err());
+
+        fs::write(dir.join("01-1.txt"), "3   4\n4   3\n").unwrap();
+        fs::write(dir.join("01-1.expected"), "# part1 = <answer>\n# part2 = <answer>\n").unwrap();
+        let err = check_day(solution, dir.path()).unwrap_err();
+        assert!(err.to_string().starts_with("Day 01 has no expected answers, fill them in "));
+
+        fs::write(dir.join("01-1.expected"), "part1 = 0\n").unwrap();
+        let outcomes = check_day(solution, dir.path()).unwrap();
+        assert_eq!(1, outcomes.len());
+        assert!(outcomes[0].passed());
+    }
+}
@@ -0,0 +1,327 @@
+use anyhow::{ensure, Result};
+use std::fmt;
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+use crate::direction::{Dir8, Direction};
+use crate::point::Point;
+
+/// Rectangular 2D grid stored row-major, addressed as `(row, col)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    tiles: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            tiles: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from rows, failing if they are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+
+        let mut tiles = Vec::with_capacity(width * height);
+        for (row, line) in rows.into_iter().enumerate() {
+            ensure!(
+                line.len() == width,
+                "grid row {} has length {}, expected {}",
+                row,
+                line.len(),
+                width
+            );
+            tiles.extend(line);
+        }
+
+        Ok(Self {
+            tiles,
+            width,
+            height,
+        })
+    }
+
+    /// Parses one tile per character, one row per line.
+    pub fn parse_with<R: BufRead, F: FnMut(char) -> T>(reader: R, mut f: F) -> Result<Self> {
+        let rows = reader
+            .lines()
+            .map(|line| Ok(line?.chars().map(&mut f).collect()))
+            .collect::<Result<Vec<Vec<T>>>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.in_bounds(row, col)
+            .then(|| &self.tiles[row * self.width + col])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if !self.in_bounds(row, col) {
+            return None;
+        }
+
+        Some(&mut self.tiles[row * self.width + col])
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point
+            .to_index()
+            .is_some_and(|(row, col)| self.in_bounds(row, col))
+    }
+
+    pub fn get_point(&self, point: Point) -> Option<&T> {
+        let (row, col) = point.to_index()?;
+        self.get(row, col)
+    }
+
+    pub fn get_point_mut(&mut self, point: Point) -> Option<&mut T> {
+        let (row, col) = point.to_index()?;
+        self.get_mut(row, col)
+    }
+
+    /// Orthogonal neighbours (up, right, down, left) that lie inside the grid.
+    pub fn neighbours(&self, pos: (usize, usize)) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours_by(pos, Direction::ALL.map(Direction::offset))
+    }
+
+    /// Orthogonal and diagonal neighbours, clockwise from above, that lie inside the grid.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours_by(pos, Dir8::ALL.map(Dir8::offset))
+    }
+
+    fn neighbours_by<const N: usize>(
+        &self,
+        pos: (usize, usize),
+        offsets: [Point; N],
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let origin = Point::from_index(pos);
+        offsets
+            .into_iter()
+            .filter_map(move |offset| (origin? + offset).to_index())
+            .filter_map(|(row, col)| Some(((row, col), self.get(row, col)?)))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.tiles[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on zero, an empty-width grid simply has no rows
+        self.tiles.chunks(self.width.max(1)).take(self.height)
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let width = self.width;
+        self.tiles
+            .iter()
+            .skip(col)
+            .step_by(width.max(1))
+            .take(if col < width { self.height } else { 0 })
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// All `(row, col)` positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All tiles together with their `(row, col)` position in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.tiles.iter())
+    }
+
+    /// Position of the first tile (row-major) matching the predicate.
+    pub fn position<P: FnMut(&T) -> bool>(&self, mut predicate: P) -> Option<(usize, usize)> {
+        self.tiles
+            .iter()
+            .position(&mut predicate)
+            .map(|idx| (idx / self.width, idx % self.width))
+    }
+
+    /// Positions of all tiles matching the predicate.
+    pub fn positions_where<'a, P: FnMut(&T) -> bool + 'a>(
+        &'a self,
+        mut predicate: P,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.iter()
+            .filter(move |(_, tile)| predicate(tile))
+            .map(|(pos, _)| pos)
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Position of the first tile equal to `needle`.
+    pub fn find(&self, needle: &T) -> Option<(usize, usize)> {
+        self.position(|tile| tile == needle)
+    }
+}
+
+impl Grid<char> {
+    pub fn parse<R: BufRead>(reader: R) -> Result<Self> {
+        Self::parse_with(reader, |c| c)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        self.get(row, col).unwrap_or_else(|| {
+            panic!(
+                "position ({}, {}) out of bounds for {}x{} grid",
+                row, col, self.height, self.width
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(row, col).unwrap_or_else(|| {
+            panic!(
+                "position ({}, {}) out of bounds for {}x{} grid",
+                row, col, height, width
+            )
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get_point(point)
+            .unwrap_or_else(|| panic!("{} out of bounds for {}x{} grid", point, self.height, self.width))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        let (height, width) = (self.height, self.width);
+        self.get_point_mut(point)
+            .unwrap_or_else(|| panic!("{} out of bounds for {}x{} grid", point, height, width))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+
+            for tile in row {
+                write!(f, "{}", tile)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST: &str = "\
+..#
+.^.
+#..
+";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(TEST.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn parses_dimensions() {
+        let grid = grid();
+        assert_eq!(3, grid.width());
+        assert_eq!(3, grid.height());
+        assert_eq!(Some(&'#'), grid.get(0, 2));
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!(None, grid.get(0, 3));
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert!(Grid::parse("..\n...\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn finds_positions() {
+        let grid = grid();
+        assert_eq!(Some((1, 1)), grid.find(&'^'));
+        assert_eq!(None, grid.find(&'X'));
+        assert_eq!(
+            vec![(0, 2), (2, 0)],
+            grid.positions_where(|&c| c == '#').collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = grid();
+        let corner = grid.neighbours((0, 0)).map(|(pos, _)| pos).collect::<Vec<_>>();
+        assert_eq!(vec![(0, 1), (1, 0)], corner);
+        assert_eq!(3, grid.neighbours8((0, 0)).count());
+        assert_eq!(8, grid.neighbours8((1, 1)).count());
+    }
+
+    #[test]
+    fn point_lookup() {
+        let mut grid = grid();
+        assert!(grid.contains(Point::new(2, 2)));
+        assert!(!grid.contains(Point::new(-1, 0)));
+        assert!(!grid.contains(Point::new(0, 3)));
+        assert_eq!(Some(&'^'), grid.get_point(Point::new(1, 1)));
+        assert_eq!(None, grid.get_point(Point::new(1, -1)));
+
+        grid[Point::new(0, 0)] = '#';
+        assert_eq!('#', grid[(0, 0)]);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(Some(&['.', '^', '.'][..]), grid.row(1));
+        assert_eq!(3, grid.rows().count());
+        assert_eq!(vec!['#', '.', '.'], grid.column(2).copied().collect::<Vec<_>>());
+        assert_eq!(0, grid.column(3).count());
+        assert_eq!(3, grid.columns().count());
+    }
+
+    #[test]
+    fn mutates_and_displays() {
+        let mut grid = grid();
+        grid[(1, 1)] = '.';
+        *grid.get_mut(2, 2).unwrap() = 'O';
+        assert_eq!(None, grid.get_mut(5, 5));
+        assert_eq!("..#\n...\n#.O", grid.to_string());
+    }
+}
@@ -0,0 +1,294 @@
+use anyhow::*;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::bench::Stats;
+use crate::records;
+use crate::solution::Part;
+
+/// Where benchmark results are appended unless `--history` says otherwise.
+pub const DEFAULT_PATH: &str = "benchmarks/history.csv";
+
+const HEADER: &str = "timestamp,rev,day,part,input_hash,samples,min_ns,median_ns,mean_ns,p95_ns,stddev_ns";
+
+/// What a history entry timed: parsing the input or solving a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Step {
+    Parse,
+    Part(Part),
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Part(part) => write!(f, "{}", part),
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "parse" => Ok(Step::Parse),
+            part => Ok(Step::Part(part.parse()?)),
+        }
+    }
+}
+
+/// One benchmarked step of one day in one run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Milliseconds since the Unix epoch, shared by every entry of a run.
+    pub timestamp: u64,
+    /// Git revision the run was built from, suffixed with `-dirty` for uncommitted changes.
+    pub rev: String,
+    pub day: u8,
+    /// Stored in the `part` column as `parse`, `1` or `2`.
+    pub step: Step,
+    pub input_hash: u64,
+    pub stats: Stats,
+}
+
+impl Entry {
+    fn parse(line: &str) -> Option<Self> {
+        let [timestamp, rev, day, part, input_hash, samples, min, median, mean, p95, stddev] = records::fields(line)?;
+        let nanos = |field: &str| field.parse().ok().map(Duration::from_nanos);
+
+        Some(Self {
+            timestamp: timestamp.parse().ok()?,
+            rev: rev.to_string(),
+            day: day.parse().ok()?,
+            step: part.parse().ok()?,
+            input_hash: u64::from_str_radix(input_hash, 16).ok()?,
+            stats: Stats {
+                samples: samples.parse().ok()?,
+                min: nanos(min)?,
+                median: nanos(median)?,
+                mean: nanos(mean)?,
+                p95: nanos(p95)?,
+                stddev: nanos(stddev)?,
+            },
+        })
+    }
+
+    fn to_line(&self) -> String {
+        let stats = &self.stats;
+        format!(
+            "{},{},{},{},{:016x},{},{},{},{},{},{}",
+            self.timestamp,
+            self.rev,
+            self.day,
+            self.step,
+            self.input_hash,
+            stats.samples,
+            stats.min.as_nanos(),
+            stats.median.as_nanos(),
+            stats.mean.as_nanos(),
+            stats.p95.as_nanos(),
+            stats.stddev.as_nanos()
+        )
+    }
+
+    fn same_step(&self, other: &Entry) -> bool {
+        (self.day, self.step, self.input_hash) == (other.day, other.step, other.input_hash)
+    }
+}
+
+/// Every benchmark result recorded so far, oldest first, kept as an
+/// append-only CSV file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl History {
+    /// Loads the history at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = records::load(&path, "history", Self::parse)?;
+        Ok(Self { path, entries })
+    }
+
+    fn parse(text: &str) -> Result<Vec<Entry>> {
+        records::parse_csv(text, HEADER, Entry::parse)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Records the entries of a run in the file.
+    pub fn append(&mut self, entries: &[Entry]) -> Result<()> {
+        records::append_csv(&self.path, HEADER, entries.iter().map(Entry::to_line))?;
+        self.entries.extend_from_slice(entries);
+        Ok(())
+    }
+
+    /// Entries of the most recent run.
+    pub fn latest_run(&self) -> &[Entry] {
+        let Some(last) = self.entries.last() else {
+            return &[];
+        };
+
+        let start = self
+            .entries
+            .iter()
+            .rposition(|entry| (entry.timestamp, &entry.rev) != (last.timestamp, &last.rev))
+            .map_or(0, |idx| idx + 1);
+        &self.entries[start..]
+    }
+
+    /// Compares every step of the latest run with the most recent earlier
+    /// result for the same day, step and input, only considering revisions starting
+    /// with `baseline_rev` if given.
+    pub fn compare(&self, baseline_rev: Option<&str>) -> Vec<Comparison> {
+        let latest = self.latest_run();
+        let earlier = &self.entries[..self.entries.len() - latest.len()];
+
+        latest
+            .iter()
+            .map(|entry| Comparison {
+                latest: entry.clone(),
+                baseline: earlier
+                    .iter()
+                    .rev()
+                    .filter(|candidate| baseline_rev.is_none_or(|rev| candidate.rev.starts_with(rev)))
+                    .find(|candidate| candidate.same_step(entry))
+                    .cloned(),
+            })
+            .collect()
+    }
+}
+
+/// A step of the latest run next to its baseline, if there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub latest: Entry,
+    pub baseline: Option<Entry>,
+}
+
+impl Comparison {
+    /// Change of the median time in percent, positive when it got slower.
+    pub fn change(&self) -> Option<f64> {
+        let baseline = self.baseline.as_ref()?.stats.median.as_secs_f64();
+        let latest = self.latest.stats.median.as_secs_f64();
+        (baseline > 0.0).then(|| (latest - baseline) / baseline * 100.0)
+    }
+
+    /// Whether the step slowed down by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+/// Milliseconds since the Unix epoch.
+pub fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+/// Short hash of the crate's `HEAD`, `unknown` outside of a git checkout.
+pub fn git_rev() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(rev) if git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{}-dirty", rev)
+        }
+        Some(rev) => rev,
+        None => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: u64, rev: &str, day: u8, step: Step, median_ms: u64) -> Entry {
+        let median = Duration::from_millis(median_ms);
+        Entry {
+            timestamp,
+            rev: rev.to_string(),
+            day,
+            step,
+            input_hash: 0xabc,
+            stats: Stats {
+                samples: 20,
+                min: median,
+                median,
+                mean: median,
+                p95: median,
+                stddev: Duration::ZERO,
+            },
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let entries = vec![
+            entry(1, "abc1234", 5, Step::Parse, 1),
+            entry(1, "abc1234", 5, Step::Part(Part::One), 3),
+            entry(1, "abc1234-dirty", 5, Step::Part(Part::Two), 4),
+        ];
+        let text = format!(
+            "{}\n{}\n{}\n{}\n",
+            HEADER,
+            entries[0].to_line(),
+            entries[1].to_line(),
+            entries[2].to_line()
+        );
+        assert!(text.contains("\n1,abc1234,5,parse,"));
+
+        assert_eq!(entries, History::parse(&text).unwrap());
+        assert!(History::parse("1,abc,5,1").is_err());
+    }
+
+    #[test]
+    fn compares_against_earlier_runs() {
+        let history = History {
+            path: PathBuf::new(),
+            entries: vec![
+                entry(1, "aaa", 5, Step::Part(Part::One), 100),
+                entry(1, "aaa", 5, Step::Part(Part::Two), 100),
+                entry(1, "aaa", 5, Step::Parse, 10),
+                entry(2, "bbb", 5, Step::Part(Part::One), 200),
+                entry(3, "ccc", 5, Step::Part(Part::One), 250),
+                entry(3, "ccc", 5, Step::Part(Part::Two), 105),
+                entry(3, "ccc", 6, Step::Part(Part::One), 10),
+                entry(3, "ccc", 5, Step::Parse, 20),
+            ],
+        };
+
+        assert_eq!(4, history.latest_run().len());
+
+        let comparisons = history.compare(None);
+        assert_eq!(Some(25.0), comparisons[0].change().map(f64::round));
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[1].is_regression(10.0));
+        assert_eq!(None, comparisons[2].baseline);
+        assert_eq!(Some(100.0), comparisons[3].change().map(f64::round));
+        assert!(comparisons[3].is_regression(10.0));
+
+        let comparisons = history.compare(Some("aaa"));
+        assert_eq!(Some(150.0), comparisons[0].change().map(f64::round));
+    }
+}
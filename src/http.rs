@@ -0,0 +1,294 @@
+use anyhow::*;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// How long to wait for a server before giving up.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A minimal HTTP request. Plain `http://` URLs are spoken to directly,
+/// `https://` ones go through `curl`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+impl Request {
+    pub fn get(url: impl Into<String>) -> Self {
+        Self {
+            method: "GET",
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// A POST of `application/x-www-form-urlencoded` fields.
+    pub fn post_form(url: impl Into<String>, fields: &[(&str, &str)]) -> Self {
+        let body = fields
+            .iter()
+            .map(|(name, value)| format!("{}={}", url_encode(name), url_encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        Self {
+            method: "POST",
+            url: url.into(),
+            headers: vec![(
+                "Content-Type".to_string(),
+                "application/x-www-form-urlencoded".to_string(),
+            )],
+            body: Some(body),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn send(&self) -> Result<Response> {
+        if let Some(rest) = self.url.strip_prefix("http://") {
+            self.send_plain(rest)
+        } else if self.url.starts_with("https://") {
+            self.send_curl()
+        } else {
+            bail!("unsupported URL '{}', expected http:// or https://", self.url)
+        }
+    }
+
+    fn send_plain(&self, rest: &str) -> Result<Response> {
+        let (host, path) = match rest.find('/') {
+            Some(idx) => rest.split_at(idx),
+            None => (rest, "/"),
+        };
+        let address = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:80", host)
+        };
+
+        let mut stream = TcpStream::connect(&address).with_context(|| format!("failed to connect to {}", host))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+
+        let mut request = format!("{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n", self.method, path, host);
+        for (name, value) in &self.headers {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        if let Some(body) = &self.body {
+            request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+        }
+        request.push_str("\r\n");
+        request.push_str(self.body.as_deref().unwrap_or_default());
+
+        stream
+            .write_all(request.as_bytes())
+            .with_context(|| format!("failed to send request to {}", host))?;
+        let mut raw = Vec::new();
+        stream
+            .read_to_end(&mut raw)
+            .with_context(|| format!("failed to read response from {}", host))?;
+
+        parse_response(&raw)
+    }
+
+    fn send_curl(&self) -> Result<Response> {
+        // the config goes through stdin so the session cookie does not show up
+        // in the process list
+        let mut config = format!("url = {}\nrequest = {}\n", quote(&self.url), self.method);
+        for (name, value) in &self.headers {
+            config.push_str(&format!("header = {}\n", quote(&format!("{}: {}", name, value))));
+        }
+        if let Some(body) = &self.body {
+            config.push_str(&format!("data-binary = {}\n", quote(body)));
+        }
+
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--max-time", &TIMEOUT.as_secs().to_string()])
+            .args(["--write-out", "\n%{http_code}", "--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("failed to run curl, which is needed for https")?;
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(config.as_bytes())
+            .context("failed to pass the request to curl")?;
+
+        let output = child.wait_with_output().context("failed to run curl")?;
+        ensure!(
+            output.status.success(),
+            "request to {} failed: {}",
+            self.url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout.rsplit_once('\n').context("curl did not report a status")?;
+        Ok(Response {
+            status: status.trim().parse().context("curl reported an invalid status")?,
+            body: body.to_string(),
+        })
+    }
+}
+
+/// Quotes a value for a curl config file.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// Percent-encodes everything but unreserved characters.
+pub fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn parse_response(raw: &[u8]) -> Result<Response> {
+    let split = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .context("malformed HTTP response, no end of headers")?;
+    let head = String::from_utf8_lossy(&raw[..split]);
+    let body = &raw[split + 4..];
+
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok())
+        .context("malformed HTTP status line")?;
+
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding") && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    let body = if chunked { dechunk(body)? } else { body.to_vec() };
+
+    Ok(Response {
+        status,
+        body: String::from_utf8(body).context("response is not valid UTF-8")?,
+    })
+}
+
+fn dechunk(mut raw: &[u8]) -> Result<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let line_end = raw
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .context("malformed chunked response")?;
+        let size = std::str::from_utf8(&raw[..line_end])
+            .ok()
+            .and_then(|line| usize::from_str_radix(line.split(';').next()?.trim(), 16).ok())
+            .context("malformed chunk size")?;
+        raw = &raw[line_end + 2..];
+
+        if size == 0 {
+            return Ok(body);
+        }
+        ensure!(raw.len() >= size + 2, "truncated chunked response");
+        body.extend_from_slice(&raw[..size]);
+        raw = &raw[size + 2..];
+    }
+}
+
+/// A one-shot HTTP server for tests, answering the first request with
+/// `response` and handing back the raw request it received.
+#[cfg(test)]
+pub(crate) fn serve_once(response: &str) -> (String, std::thread::JoinHandle<String>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let response = response.to_string();
+
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0; 4096];
+        loop {
+            let len = stream.read(&mut buffer).unwrap();
+            request.extend_from_slice(&buffer[..len]);
+
+            let text = String::from_utf8_lossy(&request);
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .map_or(0, |length| length.parse().unwrap());
+                if len == 0 || body.len() >= length {
+                    break;
+                }
+            }
+        }
+
+        stream.write_all(response.as_bytes()).unwrap();
+        String::from_utf8(request).unwrap()
+    });
+
+    (url, server)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_responses() {
+        let response = parse_response(b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\n1 2\n").unwrap();
+        assert_eq!(Response { status: 200, body: "1 2\n".to_string() }, response);
+
+        let chunked = b"HTTP/1.1 404 Not Found\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2;x=y\r\nde\r\n0\r\n\r\n";
+        assert_eq!(Response { status: 404, body: "abcde".to_string() }, parse_response(chunked).unwrap());
+
+        assert!(parse_response(b"HTTP/1.1 200 OK").is_err());
+    }
+
+    #[test]
+    fn encodes_forms() {
+        let request = Request::post_form("http://x/", &[("level", "1"), ("answer", "a b&c")]);
+        assert_eq!(Some("level=1&answer=a%20b%26c".to_string()), request.body);
+        assert_eq!("%C3%A9~", url_encode("é~"));
+    }
+
+    #[test]
+    fn talks_plain_http() {
+        let (url, server) = serve_once("HTTP/1.1 201 Created\r\n\r\ndone");
+
+        let response = Request::post_form(format!("{}/submit", url), &[("a", "1")])
+            .header("X-Test", "yes")
+            .send()
+            .unwrap();
+        assert_eq!(201, response.status);
+        assert_eq!("done", response.body);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /submit HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nX-Test: yes\r\n"));
+        assert!(request.ends_with("\r\n\r\na=1"));
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::*;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Environment variable overriding the directory that holds `NN.txt` inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a day reads its puzzle input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `NN.txt` in `$AOC_INPUT_DIR`, or in `input/` when it is not set.
+    #[default]
+    Default,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Interprets a command line argument, `-` meaning stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(PathBuf::from(path)),
+        }
+    }
+
+    /// Reads the input and [`normalize`]s it.
+    pub fn read(&self, day: u8) -> Result<String> {
+        self.read_raw(day).map(|input| normalize(&input))
+    }
+
+    fn read_raw(&self, day: u8) -> Result<String> {
+        match self {
+            InputSource::Default => {
+                let path = default_path(day);
+                if !path.exists() {
+                    bail!(
+                        "input for Day {:02} not found, expected it at {} \
+                         (download it with `aoc fetch {}`, pass a path or - for stdin, or set {})",
+                        day,
+                        path.display(),
+                        day,
+                        INPUT_DIR_VAR
+                    );
+                }
+
+                fs::read_to_string(&path)
+                    .with_context(|| format!("failed to read {}", path.display()))
+            }
+            InputSource::Path(path) => fs::read_to_string(path)
+                .with_context(|| format!("failed to read input for Day {:02} from {}", day, path.display())),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("failed to read input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Normalizes line endings to `\n`, strips a UTF-8 byte order mark, trailing
+/// whitespace on every line and trailing blank lines. Non-empty input always
+/// ends with a single newline.
+pub fn normalize(raw: &str) -> String {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+
+    let mut normalized = String::with_capacity(raw.len());
+    for line in raw.split("\r\n").flat_map(|line| line.split(['\r', '\n'])) {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+
+    normalized
+}
+
+/// The location a day's input is expected at when no source is given.
+pub fn default_path(day: u8) -> PathBuf {
+    let dir = env::var_os(INPUT_DIR_VAR).map_or_else(|| PathBuf::from("input"), PathBuf::from);
+    dir.join(format!("{:02}.txt", day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_arguments() {
+        assert_eq!(InputSource::Stdin, InputSource::from_arg("-"));
+        assert_eq!(
+            InputSource::Path(PathBuf::from("other/05.txt")),
+            InputSource::from_arg("other/05.txt")
+        );
+    }
+
+    #[test]
+    fn normalizes_line_endings_and_whitespace() {
+        assert_eq!("ab\ncd\n", normalize("ab\r\ncd\r\n"));
+        assert_eq!("ab\ncd\n", normalize("\u{feff}ab  \ncd\t\n\n\n"));
+        assert_eq!("ab\n\ncd\n", normalize("ab\r\n\r\ncd"));
+        assert_eq!("ab\ncd\n", normalize("ab\rcd\r"));
+        assert_eq!("  ab\n", normalize("  ab  "));
+        assert_eq!("", normalize("\u{feff}\r\n"));
+    }
+
+    #[test]
+    fn missing_file_names_location() {
+        let err = InputSource::Path(PathBuf::from("does/not/exist.txt"))
+            .read(5)
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("does/not/exist.txt"));
+    }
+}
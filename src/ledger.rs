@@ -0,0 +1,220 @@
+use anyhow::*;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::client::Hint;
+use crate::records;
+use crate::solution::Part;
+
+const HEADER: &str = "day,input_hash,part,answer,verdict";
+
+/// Where the rejected answers are kept next to the answers file at `answers`,
+/// `answers/2024.rejected.csv` for `answers/2024.toml`.
+pub fn default_path(answers: &Path) -> PathBuf {
+    answers.with_extension("rejected.csv")
+}
+
+/// An answer the site said was wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rejection {
+    pub day: u8,
+    pub input_hash: u64,
+    pub part: Part,
+    pub answer: usize,
+    pub hint: Option<Hint>,
+}
+
+impl Rejection {
+    fn parse(line: &str) -> Option<Self> {
+        let [day, input_hash, part, answer, verdict] = records::fields(line)?;
+
+        Some(Self {
+            day: day.parse().ok()?,
+            input_hash: u64::from_str_radix(input_hash, 16).ok()?,
+            part: part.parse().ok()?,
+            answer: answer.parse().ok()?,
+            hint: match verdict {
+                "too_high" => Some(Hint::TooHigh),
+                "too_low" => Some(Hint::TooLow),
+                "wrong" => None,
+                _ => return None,
+            },
+        })
+    }
+
+    fn to_line(self) -> String {
+        let verdict = match self.hint {
+            Some(Hint::TooHigh) => "too_high",
+            Some(Hint::TooLow) => "too_low",
+            None => "wrong",
+        };
+        format!("{},{:016x},{},{},{}", self.day, self.input_hash, self.part, self.answer, verdict)
+    }
+
+    fn describe(self) -> &'static str {
+        match self.hint {
+            Some(Hint::TooHigh) => "too high",
+            Some(Hint::TooLow) => "too low",
+            None => "wrong",
+        }
+    }
+}
+
+/// What the rejected answers of a part imply: the answer is above `low` and
+/// below `high`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bounds {
+    /// Largest answer that was too low.
+    pub low: Option<usize>,
+    /// Smallest answer that was too high.
+    pub high: Option<usize>,
+}
+
+impl Bounds {
+    pub fn contains(&self, answer: usize) -> bool {
+        self.low.is_none_or(|low| answer > low) && self.high.is_none_or(|high| answer < high)
+    }
+}
+
+impl fmt::Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.low, self.high) {
+            (Some(low), Some(high)) => write!(f, "above {} and below {}", low, high),
+            (Some(low), None) => write!(f, "above {}", low),
+            (None, Some(high)) => write!(f, "below {}", high),
+            (None, None) => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Every answer the site rejected, kept as an append-only CSV file so that a
+/// known wrong answer is never submitted again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ledger {
+    path: PathBuf,
+    rejections: Vec<Rejection>,
+}
+
+impl Ledger {
+    /// Loads the ledger at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let rejections = records::load(&path, "ledger", Self::parse)?;
+        Ok(Self { path, rejections })
+    }
+
+    fn parse(text: &str) -> Result<Vec<Rejection>> {
+        records::parse_csv(text, HEADER, Rejection::parse)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The rejected answers of one part, oldest first.
+    pub fn rejections(&self, day: u8, input_hash: u64, part: Part) -> impl Iterator<Item = &Rejection> {
+        self.rejections
+            .iter()
+            .filter(move |rejection| (rejection.day, rejection.input_hash, rejection.part) == (day, input_hash, part))
+    }
+
+    pub fn bounds(&self, day: u8, input_hash: u64, part: Part) -> Bounds {
+        self.rejections(day, input_hash, part)
+            .fold(Bounds::default(), |bounds, rejection| match rejection.hint {
+                Some(Hint::TooLow) => Bounds {
+                    low: bounds.low.max(Some(rejection.answer)),
+                    ..bounds
+                },
+                Some(Hint::TooHigh) => Bounds {
+                    high: Some(bounds.high.map_or(rejection.answer, |high| high.min(rejection.answer))),
+                    ..bounds
+                },
+                None => bounds,
+            })
+    }
+
+    /// Fails with the reason if `answer` was rejected before or is ruled out
+    /// by the bounds of earlier rejections.
+    pub fn check(&self, day: u8, input_hash: u64, part: Part, answer: usize) -> Result<()> {
+        if let Some(rejection) = self
+            .rejections(day, input_hash, part)
+            .find(|rejection| rejection.answer == answer)
+        {
+            bail!(
+                "Day {:02} part {}: {} was rejected before as {}",
+                day,
+                part,
+                answer,
+                rejection.describe()
+            );
+        }
+
+        let bounds = self.bounds(day, input_hash, part);
+        ensure!(
+            bounds.contains(answer),
+            "Day {:02} part {}: {} cannot be right, earlier answers show it is {}",
+            day,
+            part,
+            answer,
+            bounds
+        );
+
+        Ok(())
+    }
+
+    /// Records the rejection in the file.
+    pub fn add(&mut self, rejection: Rejection) -> Result<()> {
+        records::append_csv(&self.path, HEADER, [rejection.to_line()])?;
+        self.rejections.push(rejection);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ScratchDir;
+
+    fn rejection(part: Part, answer: usize, hint: Option<Hint>) -> Rejection {
+        Rejection {
+            day: 5,
+            input_hash: 0xabc,
+            part,
+            answer,
+            hint,
+        }
+    }
+
+    #[test]
+    fn refuses_ruled_out_answers() {
+        let dir = ScratchDir::new("ledger");
+        let path = default_path(&dir.join("2024.toml"));
+
+        let mut ledger = Ledger::load(&path).unwrap();
+        assert!(ledger.check(5, 0xabc, Part::One, 100).is_ok());
+
+        ledger.add(rejection(Part::One, 150, Some(Hint::TooHigh))).unwrap();
+        ledger.add(rejection(Part::One, 90, Some(Hint::TooLow))).unwrap();
+        ledger.add(rejection(Part::One, 120, None)).unwrap();
+        ledger.add(rejection(Part::One, 200, Some(Hint::TooHigh))).unwrap();
+        ledger.add(rejection(Part::Two, 50, Some(Hint::TooLow))).unwrap();
+
+        let ledger = Ledger::load(&path).unwrap();
+        assert_eq!(dir.join("2024.rejected.csv"), ledger.path());
+        assert_eq!(Bounds { low: Some(90), high: Some(150) }, ledger.bounds(5, 0xabc, Part::One));
+        assert_eq!(Bounds { low: Some(50), high: None }, ledger.bounds(5, 0xabc, Part::Two));
+        assert_eq!(Bounds::default(), ledger.bounds(5, 0xdef, Part::One));
+
+        assert!(ledger.check(5, 0xabc, Part::One, 100).is_ok());
+        assert_eq!(
+            "Day 05 part 1: 120 was rejected before as wrong",
+            ledger.check(5, 0xabc, Part::One, 120).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "Day 05 part 1: 160 cannot be right, earlier answers show it is above 90 and below 150",
+            ledger.check(5, 0xabc, Part::One, 160).unwrap_err().to_string()
+        );
+        assert!(ledger.check(5, 0xabc, Part::One, 90).is_err());
+        assert!(ledger.check(5, 0xabc, Part::Two, 40).is_err());
+        assert!(ledger.check(5, 0xdef, Part::One, 160).is_ok());
+    }
+}
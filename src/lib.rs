@@ -1,3 +1,34 @@
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod days;
+pub mod direction;
+pub mod examples;
+pub mod grid;
+pub mod history;
+pub mod http;
+pub mod input;
+pub mod ledger;
+pub mod parse;
+pub mod point;
+pub mod puzzle;
+pub mod pool;
+pub mod records;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+#[cfg(test)]
+mod testing;
+pub mod watch;
+
+pub use direction::{Dir8, Direction};
+pub use grid::Grid;
+pub use parse::{ParseError, Parser};
+pub use point::Point;
+pub use solution::{run_day, DynSolution, Part, Solution};
+
 pub fn start_day(day: &str) {
     println!("Advent of Code 2024 - Day {:0>2}", day);
 }
@@ -0,0 +1,208 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use crate::grid::Grid;
+
+/// Malformed puzzle input, pointing at the offending part of a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// Number of characters to underline, at least one caret is always drawn.
+    pub len: usize,
+    /// The whole line the error occurred on.
+    pub source_line: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = self.line.to_string().len();
+
+        writeln!(
+            f,
+            "Day {:02}: {} at line {}, column {}",
+            self.day, self.message, self.line, self.column
+        )?;
+        writeln!(f, "{:gutter$} |", "", gutter = gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{:gutter$} | {}{}",
+            "",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.len.max(1)),
+            gutter = gutter
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Helper for parsing a day's input that reports errors with their location.
+///
+/// Every `&str` passed to it must be a slice of the original input, which is
+/// what `lines`, `split`, `split_once` and friends hand out.
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    day: u8,
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(day: u8, input: &'a str) -> Self {
+        Self { day, input }
+    }
+
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.input.lines()
+    }
+
+    /// Builds an error underlining `at`, which must be a slice of the input.
+    pub fn error(&self, at: &str, message: impl Into<String>) -> ParseError {
+        let start = self.input.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset + at.len() <= self.input.len())
+            .unwrap_or(0);
+
+        let line_start = self.input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = self.input[offset..]
+            .find('\n')
+            .map_or(self.input.len(), |idx| offset + idx);
+        let source_line = self.input[line_start..line_end].trim_end_matches('\r');
+        // only underline up to the end of the first line of `at`
+        let underline_end = (offset + at.len()).min(line_start + source_line.len()).max(offset);
+
+        ParseError {
+            day: self.day,
+            line: self.input[..line_start].matches('\n').count() + 1,
+            column: self.input[line_start..offset].chars().count() + 1,
+            len: self.input[offset..underline_end].chars().count(),
+            source_line: source_line.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Builds an error pointing just past the end of `line`.
+    pub fn error_at_end(&self, line: &'a str, message: impl Into<String>) -> ParseError {
+        self.error(&line[line.len()..], message)
+    }
+
+    /// Parses a number, or any other `FromStr` value, from a slice of the input.
+    pub fn number<T: FromStr>(&self, s: &'a str) -> Result<T, ParseError> {
+        let trimmed = s.trim();
+        trimmed
+            .parse()
+            .map_err(|_| self.error(trimmed, format!("expected a number, found '{}'", trimmed)))
+    }
+
+    /// Unwraps the next field of `line`, reporting `what` was missing at its end.
+    pub fn expect(&self, field: Option<&'a str>, line: &'a str, what: &str) -> Result<&'a str, ParseError> {
+        field.ok_or_else(|| self.error_at_end(line, format!("expected {}", what)))
+    }
+
+    /// Parses the whole input as a rectangular grid of characters.
+    pub fn grid(&self) -> Result<Grid<char>, ParseError> {
+        self.grid_with(|c| c)
+    }
+
+    /// Parses the whole input as a rectangular grid, mapping every character.
+    pub fn grid_with<T, F: FnMut(char) -> T>(&self, mut f: F) -> Result<Grid<T>, ParseError> {
+        let mut lines = self.lines();
+        let first = lines
+            .next()
+            .filter(|line| !line.is_empty())
+            .ok_or_else(|| self.error(self.input, "expected a grid, found empty input"))?;
+        let width = first.chars().count();
+
+        let mut rows = vec![first.chars().map(&mut f).collect::<Vec<_>>()];
+        for line in lines {
+            let row = line.chars().map(&mut f).collect::<Vec<_>>();
+            if row.len() != width {
+                let at = line.char_indices().nth(width).map_or(&line[line.len()..], |(idx, _)| &line[idx..]);
+                return Err(self.error(
+                    at,
+                    format!("expected a rectangular grid of width {}, found a row of width {}", width, row.len()),
+                ));
+            }
+            rows.push(row);
+        }
+
+        Ok(Grid::from_rows(rows).expect("rows have equal length"))
+    }
+
+    /// Splits `s` around the first `delimiter`, failing if it is not there.
+    pub fn split_once(&self, s: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(delimiter)
+            .ok_or_else(|| self.error(s, format!("expected '{}'", delimiter)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "3   4\n4   x\n2";
+
+    #[test]
+    fn locates_errors() {
+        let parser = Parser::new(1, INPUT);
+        let line = parser.lines().nth(1).unwrap();
+        let field = line.split_whitespace().nth(1).unwrap();
+
+        let err = parser.number::<usize>(field).unwrap_err();
+        assert_eq!((2, 5, 1), (err.line, err.column, err.len));
+        assert_eq!("4   x", err.source_line);
+        assert_eq!(
+            "Day 01: expected a number, found 'x' at line 2, column 5\n  |\n2 | 4   x\n  |     ^",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn points_past_missing_fields() {
+        let parser = Parser::new(1, INPUT);
+        let line = parser.lines().nth(2).unwrap();
+        let mut fields = line.split_whitespace();
+
+        assert_eq!("2", parser.expect(fields.next(), line, "two numbers").unwrap());
+        let err = parser.expect(fields.next(), line, "two numbers").unwrap_err();
+        assert_eq!((3, 2), (err.line, err.column));
+        assert!(err.to_string().ends_with("3 | 2\n  |  ^"));
+    }
+
+    #[test]
+    fn validates_grids() {
+        let grid = Parser::new(4, "ab\ncd\n").grid().unwrap();
+        assert_eq!((2, 2), (grid.width(), grid.height()));
+
+        let err = Parser::new(4, "abc\nabcd\nabc").grid().unwrap_err();
+        assert_eq!((2, 4, 1), (err.line, err.column, err.len));
+
+        let err = Parser::new(4, "abc\nab").grid().unwrap_err();
+        assert_eq!((2, 3), (err.line, err.column));
+
+        assert!(Parser::new(4, "").grid().is_err());
+    }
+
+    #[test]
+    fn splits() {
+        let parser = Parser::new(7, "190: 10 19\n83 17 5");
+        let mut lines = parser.lines();
+
+        assert_eq!(("190", " 10 19"), parser.split_once(lines.next().unwrap(), ":").unwrap());
+        let err = parser.split_once(lines.next().unwrap(), ":").unwrap_err();
+        assert_eq!((2, 1, 7), (err.line, err.column, err.len));
+
+        let err = parser.split_once(parser.input(), "|").unwrap_err();
+        assert_eq!((1, 1, 10), (err.line, err.column, err.len));
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Signed 2D point or offset, always in `(row, col)` order.
+///
+/// Rows grow downwards and columns grow to the right, matching how the
+/// puzzle inputs are laid out on screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(row: i64, col: i64) -> Self {
+        Self { row, col }
+    }
+
+    /// Converts a `(row, col)` grid index, failing if it does not fit an `i64`.
+    pub fn from_index((row, col): (usize, usize)) -> Option<Self> {
+        Some(Self {
+            row: row.try_into().ok()?,
+            col: col.try_into().ok()?,
+        })
+    }
+
+    /// Converts back to a `(row, col)` grid index, failing for negative coordinates.
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((self.row.try_into().ok()?, self.col.try_into().ok()?))
+    }
+
+    /// Whether the point lies inside a `width` x `height` area anchored at the origin.
+    pub fn in_bounds(self, width: i64, height: i64) -> bool {
+        self.row >= 0 && self.row < height && self.col >= 0 && self.col < width
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        (self.row - other.row).abs() + (self.col - other.col).abs()
+    }
+
+    pub fn chebyshev(self, other: Point) -> i64 {
+        (self.row - other.row).abs().max((self.col - other.col).abs())
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            row: self.row + rhs.row,
+            col: self.col + rhs.col,
+        }
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            row: self.row - rhs.row,
+            col: self.col - rhs.col,
+        }
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            row: -self.row,
+            col: -self.col,
+        }
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self {
+            row: self.row * rhs,
+            col: self.col * rhs,
+        }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(row {}, col {})", self.row, self.col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -3);
+
+        assert_eq!(Point::new(5, -1), a + b);
+        assert_eq!(Point::new(3, -5), b - a);
+        assert_eq!(Point::new(-1, -2), -a);
+        assert_eq!(Point::new(3, 6), a * 3);
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(b, c);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -3);
+
+        assert_eq!(8, a.manhattan(b));
+        assert_eq!(5, a.chebyshev(b));
+        assert_eq!(0, a.manhattan(a));
+    }
+
+    #[test]
+    fn index_conversion() {
+        assert_eq!(Some(Point::new(3, 7)), Point::from_index((3, 7)));
+        assert_eq!(None, Point::from_index((usize::MAX, 0)));
+        assert_eq!(Some((3, 7)), Point::new(3, 7).to_index());
+        assert_eq!(None, Point::new(-1, 7).to_index());
+        assert_eq!(None, Point::new(3, -7).to_index());
+    }
+
+    #[test]
+    fn bounds() {
+        assert!(Point::new(0, 0).in_bounds(3, 2));
+        assert!(Point::new(1, 2).in_bounds(3, 2));
+        assert!(!Point::new(2, 0).in_bounds(3, 2));
+        assert!(!Point::new(0, 3).in_bounds(3, 2));
+        assert!(!Point::new(-1, 0).in_bounds(3, 2));
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Calls `f` on every item from `threads` worker threads and hands the results
+/// to `consume` in the order of `items`, each as soon as all earlier ones are
+/// done. Workers pick up items in order, so early items finish first.
+pub fn for_each_ordered<T, R, F, C>(threads: usize, items: &[T], f: F, mut consume: C)
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+    C: FnMut(R),
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                if sender.send((idx, f(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+        for (idx, result) in receiver {
+            pending.insert(idx, result);
+            while let Some(result) = pending.remove(&expected) {
+                consume(result);
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn keeps_the_order() {
+        let items = (0..20u64).collect::<Vec<_>>();
+        let mut results = Vec::new();
+
+        for_each_ordered(
+            4,
+            &items,
+            |&item| {
+                thread::sleep(Duration::from_millis(20 - item));
+                item * 2
+            },
+            |result| results.push(result),
+        );
+
+        assert_eq!(items.iter().map(|item| item * 2).collect::<Vec<_>>(), results);
+    }
+
+    #[test]
+    fn handles_edge_cases() {
+        let mut results = Vec::new();
+        for_each_ordered(0, &[1, 2, 3], |&item| item, |result| results.push(result));
+        assert_eq!(vec![1, 2, 3], results);
+
+        for_each_ordered(8, &[] as &[u8], |&item| item, |_| panic!("no items"));
+    }
+}
@@ -0,0 +1,465 @@
+use anyhow::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::client::DEFAULT_BASE_URL;
+use crate::solution::Part;
+
+/// Directory the puzzle descriptions are archived in.
+pub const PUZZLES_DIR: &str = "puzzles";
+
+/// Where the description of `day` is archived.
+pub fn default_path(day: u8) -> PathBuf {
+    Path::new(PUZZLES_DIR).join(format!("{:02}.md", day))
+}
+
+/// A piece of an HTML page: text with entities decoded, or a tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token<'a> {
+    Text(String),
+    Open { name: String, attrs: &'a str },
+    Close { name: String },
+}
+
+/// Splits `html` into tags and text, dropping comments.
+pub(crate) fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, rest)| rest);
+            continue;
+        }
+
+        let Some(tag) = rest.strip_prefix('<') else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+            continue;
+        };
+
+        let (tag, after) = tag.split_once('>').unwrap_or((tag, ""));
+        rest = after;
+        let tag = tag.trim_end_matches('/');
+        let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        match name.strip_prefix('/') {
+            Some(name) => tokens.push(Token::Close { name: name.to_ascii_lowercase() }),
+            None => tokens.push(Token::Open {
+                name: name.to_ascii_lowercase(),
+                attrs,
+            }),
+        }
+    }
+
+    tokens
+}
+
+/// Value of the attribute `name` in a tag's attribute list.
+pub(crate) fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let start = attrs.find(&format!("{}=\"", name))? + name.len() + 2;
+    let len = attrs[start..].find('"')?;
+    Some(&attrs[start..start + len])
+}
+
+/// Replaces the named and numeric character references used on the site.
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..].find(';').map(|end| &rest[1..end + 1]);
+        let c = entity.and_then(|entity| match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let code = match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => entity.strip_prefix('#')?.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+
+        match (c, entity) {
+            (Some(c), Some(entity)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The tokens of every `<article>` of a puzzle page, one entry per part.
+pub(crate) fn articles<'a>(tokens: &[Token<'a>]) -> Vec<Vec<Token<'a>>> {
+    let mut articles = Vec::new();
+    let mut current: Option<Vec<Token>> = None;
+
+    for token in tokens {
+        match token {
+            Token::Open { name, .. } if name == "article" => current = Some(Vec::new()),
+            Token::Close { name } if name == "article" => articles.extend(current.take()),
+            token => {
+                if let Some(article) = &mut current {
+                    article.push(token.clone());
+                }
+            }
+        }
+    }
+
+    articles
+}
+
+/// Wraps `code` in enough backticks that none inside ends the span.
+fn code_span(code: &str) -> String {
+    let mut fence = "`".to_string();
+    while code.contains(&fence) {
+        fence.push('`');
+    }
+    let pad = if code.starts_with('`') || code.ends_with('`') { " " } else { "" };
+    format!("{}{}{}{}{}", fence, pad, code, pad, fence)
+}
+
+/// Converts one article to Markdown. Headings lose their `---` decoration,
+/// `<em>` becomes `*emphasis*`, emphasized inline code `*`code`*`, `<pre>`
+/// a fenced block and links point to the site.
+fn article_to_markdown(tokens: &[Token]) -> String {
+    let mut markdown = String::new();
+    // inline text of the current block, whitespace is collapsed when it ends
+    let mut line = String::new();
+    // inline code being collected and whether it contained emphasis
+    let mut code: Option<(String, bool)> = None;
+    let mut pre: Option<String> = None;
+    let mut links = Vec::new();
+
+    let end_block = |markdown: &mut String, line: &mut String, prefix: &str| {
+        let text = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !text.is_empty() {
+            markdown.push_str(prefix);
+            markdown.push_str(&text);
+            markdown.push_str(if prefix == "- " { "\n" } else { "\n\n" });
+        }
+        line.clear();
+    };
+
+    for token in tokens {
+        if let Some(block) = &mut pre {
+            match token {
+                Token::Text(text) => block.push_str(text),
+                Token::Close { name } if name == "pre" => {
+                    let block = pre.take().unwrap_or_default();
+                    markdown.push_str("```\n");
+                    markdown.push_str(&block);
+                    if !block.ends_with('\n') {
+                        markdown.push('\n');
+                    }
+                    markdown.push_str("```\n\n");
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        if let Some((text, emphasized)) = &mut code {
+            match token {
+                Token::Text(part) => text.push_str(part),
+                Token::Open { name, .. } if name == "em" => *emphasized = true,
+                Token::Close { name } if name == "code" => {
+                    let (text, emphasized) = code.take().unwrap_or_default();
+                    let span = code_span(&text);
+                    if emphasized {
+                        line.push_str(&format!("*{}*", span));
+                    } else {
+                        line.push_str(&span);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        match token {
+            Token::Text(text) => line.push_str(&text.replace('*', "\\*")),
+            Token::Open { name, attrs } => match name.as_str() {
+                "pre" => {
+                    end_block(&mut markdown, &mut line, "");
+                    pre = Some(String::new());
+                }
+                "code" => code = Some((String::new(), false)),
+                "em" => line.push('*'),
+                "a" => {
+                    line.push('[');
+                    links.push(attr(attrs, "href").map(|href| match href.strip_prefix('/') {
+                        Some(path) => format!("{}/{}", DEFAULT_BASE_URL, path),
+                        None => href.to_string(),
+                    }));
+                }
+                "br" => line.push('\n'),
+                _ => {}
+            },
+            Token::Close { name } => match name.as_str() {
+                "em" => line.push('*'),
+                "a" => match links.pop().flatten() {
+                    Some(href) => line.push_str(&format!("]({})", href)),
+                    None => line.push(']'),
+                },
+                "h2" => {
+                    let heading = line.split_whitespace().collect::<Vec<_>>().join(" ");
+                    line = heading.trim_start_matches("--- ").trim_end_matches(" ---").to_string();
+                    end_block(&mut markdown, &mut line, "## ");
+                }
+                "li" => end_block(&mut markdown, &mut line, "- "),
+                "ul" | "ol" => {
+                    end_block(&mut markdown, &mut line, "");
+                    markdown.push('\n');
+                }
+                "p" => end_block(&mut markdown, &mut line, ""),
+                _ => {}
+            },
+        }
+    }
+    end_block(&mut markdown, &mut line, "");
+
+    markdown
+}
+
+/// Converts the `<article>` sections of a puzzle page to Markdown, returning
+/// it together with the number of parts found.
+pub fn to_markdown(html: &str) -> Result<(String, usize)> {
+    let articles = articles(&tokenize(html));
+    ensure!(
+        !articles.is_empty(),
+        "no puzzle description on the page, is it a saved puzzle page and the session token valid?"
+    );
+
+    let markdown = articles
+        .iter()
+        .map(|article| article_to_markdown(article).trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Ok((markdown + "\n", articles.len()))
+}
+
+/// Converts the puzzle page `html` and writes it to `path`, replacing what was
+/// there so a later run picks up part two. Returns the number of parts.
+pub fn archive(html: &str, path: &Path) -> Result<usize> {
+    let (markdown, parts) = to_markdown(html)?;
+
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    fs::write(path, markdown).with_context(|| format!("failed to write {}", path.display()))?;
+
+    Ok(parts)
+}
+
+/// A `<pre>` block of a puzzle page, a candidate example input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleBlock {
+    /// Part whose description the block is in.
+    pub part: Part,
+    pub input: String,
+}
+
+/// Every `<pre>` block of the page's articles, in order.
+pub fn example_blocks(html: &str) -> Vec<ExampleBlock> {
+    let mut blocks = Vec::new();
+
+    for (article, part) in articles(&tokenize(html)).iter().zip(Part::ALL) {
+        let mut pre: Option<String> = None;
+        for token in article {
+            match token {
+                Token::Open { name, .. } if name == "pre" => pre = Some(String::new()),
+                Token::Close { name } if name == "pre" => blocks.extend(pre.take().map(|input| ExampleBlock {
+                    part,
+                    input,
+                })),
+                Token::Text(text) => {
+                    if let Some(block) = &mut pre {
+                        block.push_str(text);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    blocks
+}
+
+/// The expected answer of the example of each part: the last emphasized
+/// number in inline code, `<code><em>11</em></code>`, of its article.
+pub fn expected_answers(html: &str) -> BTreeMap<Part, usize> {
+    let mut answers = BTreeMap::new();
+
+    for (article, part) in articles(&tokenize(html)).iter().zip(Part::ALL) {
+        // answers are emphasis opened inside inline code, neither code inside
+        // emphasis nor emphasis inside example blocks counts
+        let (mut in_pre, mut code, mut em) = (false, false, false);
+        for token in article {
+            match token {
+                Token::Open { name, .. } if name == "pre" => in_pre = true,
+                Token::Close { name } if name == "pre" => in_pre = false,
+                Token::Open { name, .. } if name == "code" => code = true,
+                Token::Close { name } if name == "code" => (code, em) = (false, false),
+                Token::Open { name, .. } if name == "em" => em = code,
+                Token::Close { name } if name == "em" => em = false,
+                Token::Text(text) if code && em && !in_pre => {
+                    if let Result::Ok(answer) = text.trim().parse() {
+                        answers.insert(part, answer);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    answers
+}
+
+/// Splits the page's expected answers among the chosen blocks: each part's
+/// answer belongs to the first chosen block of that part, its main example,
+/// and part two's to the first one of part one if none of part two was
+/// chosen, as part two mostly reuses the example. Further blocks get none,
+/// their answers are not on the page.
+pub fn assign_answers(chosen: &[&ExampleBlock], answers: &BTreeMap<Part, usize>) -> Vec<BTreeMap<Part, usize>> {
+    let mut expected = vec![BTreeMap::new(); chosen.len()];
+
+    for (&part, &answer) in answers {
+        let main = chosen
+            .iter()
+            .position(|block| block.part == part)
+            .or_else(|| chosen.iter().position(|block| block.part == Part::One));
+        if let Some(idx) = main {
+            expected[idx].insert(part, answer);
+        }
+    }
+
+    expected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><body><main>
+<script>window.addEventListener('click', function() {});</script>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is always present for the big Christmas sleigh launch.</p>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+<em>2</em>   5
+</code></pre>
+<ul>
+<li>The smallest number in the left list is <code>1</code>, and 1 &lt; 3 &amp;&amp; 2*3.</li>
+<li>See <a href="/2024/day/1/input" target="_blank">your input</a>.</li>
+</ul>
+<p>To find the <em>total distance</em>, add up all of the distances: <code><em>11</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1834060</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Use <code>a`b</code> here&#39;s &#x2014; it.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn converts_articles() {
+        let (markdown, parts) = to_markdown(PAGE).unwrap();
+        assert_eq!(2, parts);
+        assert_eq!(
+            "\
+## Day 1: Historian Hysteria
+
+The *Chief Historian* is always present for the big Christmas sleigh launch.
+
+For example:
+
+```
+3   4
+4   3
+2   5
+```
+
+- The smallest number in the left list is `1`, and 1 < 3 && 2\\*3.
+- See [your input](https://adventofcode.com/2024/day/1/input).
+
+To find the *total distance*, add up all of the distances: *`11`*.
+
+## Part Two
+
+Use ``a`b`` here's — it.
+",
+            markdown
+        );
+
+        assert!(to_markdown("<html><p>Please log in</p></html>").is_err());
+    }
+
+    #[test]
+    fn extracts_examples() {
+        let blocks = example_blocks(PAGE);
+        assert_eq!(
+            vec![ExampleBlock {
+                part: Part::One,
+                input: "3   4\n4   3\n2   5\n".to_string()
+            }],
+            blocks
+        );
+
+        // neither the 1 in plain code nor the emphasized 2 in the example block is an answer
+        assert_eq!(BTreeMap::from([(Part::One, 11)]), expected_answers(PAGE));
+
+        let page = "<article><p>Ex:</p><pre><code>a</code></pre><p><em><code>1</code></em> and <code><em>7</em></code></p></article>\
+                    <article><pre><code>b&lt;</code></pre><p>Now <code><em>x</em></code>, <code><em>48</em></code>.</p></article>";
+        let blocks = example_blocks(page);
+        assert_eq!(vec![Part::One, Part::Two], blocks.iter().map(|block| block.part).collect::<Vec<_>>());
+        assert_eq!("b<", blocks[1].input);
+        assert_eq!(BTreeMap::from([(Part::One, 7), (Part::Two, 48)]), expected_answers(page));
+
+        // neither does emphasized code after the answer
+        let page = "<article><p>Total <code><em>3</em></code>, not <em><code>4</code></em>.</p></article>";
+        assert_eq!(BTreeMap::from([(Part::One, 3)]), expected_answers(page));
+
+        // nor an example block after the answer
+        let page = "<article><p>Total <code><em>3</em></code>.</p><pre><code>1 <em>5</em>\n</code></pre></article>";
+        assert_eq!(BTreeMap::from([(Part::One, 3)]), expected_answers(page));
+    }
+
+    #[test]
+    fn assigns_answers_to_main_examples() {
+        let page = "<article><pre><code>..a\n</code></pre><pre><code>a\n</code></pre><pre><code>a.a\n</code></pre>\
+                    <p>Total <code><em>14</em></code>.</p></article>\
+                    <article><pre><code>.O.\n</code></pre><p>Now <code><em>34</em></code>.</p></article>";
+        let (blocks, answers) = (example_blocks(page), expected_answers(page));
+        let assign = |chosen: &[usize]| assign_answers(&chosen.iter().map(|&idx| &blocks[idx]).collect::<Vec<_>>(), &answers);
+        let both = BTreeMap::from([(Part::One, 14), (Part::Two, 34)]);
+
+        // the illustration of part two is not chosen, part two reuses the main example
+        assert_eq!(vec![both.clone()], assign(&[0]));
+        assert_eq!(vec![both, BTreeMap::new()], assign(&[0, 2]));
+        assert_eq!(
+            vec![BTreeMap::from([(Part::One, 14)]), BTreeMap::new(), BTreeMap::from([(Part::Two, 34)])],
+            assign(&[0, 1, 3])
+        );
+        assert_eq!(vec![BTreeMap::from([(Part::Two, 34)])], assign(&[3]));
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!("<a> & \"b\" '", decode_entities("&lt;a&gt; &amp; &quot;b&quot; &#39;"));
+        assert_eq!("a & b &unknown; &", decode_entities("a & b &unknown; &"));
+    }
+}
@@ -0,0 +1,98 @@
+//! Plain text files the runner keeps its records in: the answers file and the
+//! append-only CSV files of benchmark results and rejected answers.
+
+use anyhow::*;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+
+/// Reads the file at `path` and parses it, a missing file is `T::default()`.
+/// `kind` names the file in errors, as in "invalid history file".
+pub fn load<T, F>(path: &Path, kind: &str, parse: F) -> Result<T>
+where
+    T: Default,
+    F: FnOnce(&str) -> Result<T>,
+{
+    match fs::read_to_string(path) {
+        Result::Ok(text) => parse(&text).with_context(|| format!("invalid {} file {}", kind, path.display())),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(T::default()),
+        Err(err) => Err(err).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+/// The trimmed comma separated fields of `line`, `None` unless there are
+/// exactly `N`.
+pub fn fields<const N: usize>(line: &str) -> Option<[&str; N]> {
+    line.split(',').map(str::trim).collect::<Vec<_>>().try_into().ok()
+}
+
+/// Parses every line of a CSV file with `header` into a record, skipping blank
+/// lines and the header. Fails on the first line `parse` rejects.
+pub fn parse_csv<T>(text: &str, header: &str, parse: impl Fn(&str) -> Option<T>) -> Result<Vec<T>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && line.trim() != header)
+        .map(|(idx, line)| parse(line).with_context(|| format!("line {}: expected {}, found {}", idx + 1, header, line)))
+        .collect()
+}
+
+/// Appends `lines` to the CSV file at `path`, creating it and its directory
+/// with `header` first if needed.
+pub fn append_csv<I>(path: &Path, header: &str, lines: I) -> Result<()>
+where
+    I: IntoIterator<Item = String>,
+{
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+
+    let mut text = String::new();
+    if !path.exists() {
+        text.push_str(header);
+        text.push('\n');
+    }
+    for line in lines {
+        text.push_str(&line);
+        text.push('\n');
+    }
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ScratchDir;
+
+    const HEADER: &str = "day,answer";
+
+    fn parse(text: &str) -> Result<Vec<(u8, usize)>> {
+        parse_csv(text, HEADER, |line| {
+            let [day, answer] = fields(line)?;
+            Some((day.parse().ok()?, answer.parse().ok()?))
+        })
+    }
+
+    #[test]
+    fn appends_and_loads_records() {
+        let dir = ScratchDir::new("records");
+        let path = dir.join("nested/records.csv");
+        assert_eq!(Vec::<(u8, usize)>::new(), load(&path, "records", parse).unwrap());
+
+        append_csv(&path, HEADER, ["5,143".to_string()]).unwrap();
+        append_csv(&path, HEADER, ["6, 41".to_string(), "7,3749".to_string()]).unwrap();
+        assert_eq!("day,answer\n5,143\n6, 41\n7,3749\n", fs::read_to_string(&path).unwrap());
+        assert_eq!(vec![(5, 143), (6, 41), (7, 3749)], load(&path, "records", parse).unwrap());
+
+        assert_eq!(None, fields::<2>("5,143,1"));
+        fs::write(&path, "day,answer\n\n5,143\n6\n").unwrap();
+        let err = load(&path, "records", parse).unwrap_err();
+        assert_eq!("line 4: expected day,answer, found 6", err.root_cause().to_string());
+        assert!(err.to_string().starts_with("invalid records file "));
+    }
+}
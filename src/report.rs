@@ -0,0 +1,478 @@
+use anyhow::*;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+use itertools::Itertools;
+use crate::answers::AnswerStatus;
+use crate::runner::DayReport;
+use crate::solution::Part;
+
+/// How the runner reports its results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable output, printed day by day.
+    #[default]
+    Text,
+    /// A JSON array of [`Record`]s.
+    Json,
+    /// A CSV table of [`Record`]s with a header row.
+    Csv,
+    /// A JUnit XML report with a test case per part.
+    Junit,
+    /// A Markdown table with a row per day, for the README.
+    Markdown,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+            Format::Csv => write!(f, "csv"),
+            Format::Junit => write!(f, "junit"),
+            Format::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "junit" => Ok(Format::Junit),
+            "markdown" => Ok(Format::Markdown),
+            _ => bail!("invalid format '{}', expected text, json, csv, junit or markdown", s),
+        }
+    }
+}
+
+/// One part of one day in structured output. A day whose input could not be
+/// read or parsed has a single record without a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub title: &'static str,
+    pub part: Option<Part>,
+    pub answer: Option<usize>,
+    /// `matches`, `differs`, `new`, `timeout` or `error`.
+    pub status: &'static str,
+    /// The stored answer, if there is one.
+    pub expected: Option<usize>,
+    pub parse_time: Option<Duration>,
+    pub solve_time: Option<Duration>,
+    pub error: Option<String>,
+}
+
+const FIELDS: [&str; 9] = [
+    "day", "title", "part", "answer", "status", "expected", "parse_ns", "solve_ns", "error",
+];
+
+/// Flattens the reports into one record per part.
+pub fn records(reports: &[DayReport]) -> Vec<Record> {
+    let mut records = Vec::new();
+
+    for report in reports {
+        let record = Record {
+            day: report.day,
+            title: report.title,
+            part: None,
+            answer: None,
+            status: "error",
+            expected: None,
+            parse_time: report.parse_time,
+            solve_time: None,
+            error: None,
+        };
+
+        if let Some(err) = &report.error {
+            records.push(Record {
+                status: if report.timed_out() { "timeout" } else { "error" },
+                error: Some(format!("{:#}", err)),
+                ..record
+            });
+            continue;
+        }
+
+        for part in &report.parts {
+            let (status, expected) = match (&part.answer, part.status) {
+                (Result::Ok(answer), Some(AnswerStatus::Matches)) => ("matches", Some(*answer)),
+                (_, Some(AnswerStatus::Differs { expected })) => ("differs", Some(expected)),
+                (_, Some(AnswerStatus::New)) => ("new", None),
+                _ if part.timed_out() => ("timeout", None),
+                _ => ("error", None),
+            };
+
+            records.push(Record {
+                part: Some(part.part),
+                answer: part.answer.as_ref().ok().copied(),
+                status,
+                expected,
+                solve_time: Some(part.elapsed),
+                error: part.answer.as_ref().err().map(|err| format!("{:#}", err)),
+                ..record.clone()
+            });
+        }
+    }
+
+    records
+}
+
+impl Record {
+    /// Field values in the order of [`FIELDS`], `None` for missing values.
+    fn values(&self) -> [Option<String>; 9] {
+        let nanos = |time: Option<Duration>| time.map(|time| time.as_nanos().to_string());
+        [
+            Some(self.day.to_string()),
+            Some(self.title.to_string()),
+            self.part.map(|part| part.to_string()),
+            self.answer.map(|answer| answer.to_string()),
+            Some(self.status.to_string()),
+            self.expected.map(|expected| expected.to_string()),
+            nanos(self.parse_time),
+            nanos(self.solve_time),
+            self.error.clone(),
+        ]
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).expect("writing to a String"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Renders the records as a JSON array with one object per line.
+pub fn to_json(records: &[Record]) -> String {
+    let objects = records
+        .iter()
+        .map(|record| {
+            let fields = FIELDS
+                .iter()
+                .zip(record.values())
+                .map(|(name, value)| {
+                    let value = match (*name, value) {
+                        (_, None) => "null".to_string(),
+                        ("title" | "status" | "error", Some(value)) => json_string(&value),
+                        (_, Some(value)) => value,
+                    };
+                    format!("\"{}\": {}", name, value)
+                })
+                .collect::<Vec<_>>();
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect::<Vec<_>>();
+
+    if objects.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Renders the records as CSV with a header row, missing values left empty.
+pub fn to_csv(records: &[Record]) -> String {
+    let mut csv = FIELDS.join(",");
+    csv.push('\n');
+
+    for record in records {
+        let values = record
+            .values()
+            .map(|value| value.as_deref().map(csv_field).unwrap_or_default());
+        csv.push_str(&values.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders the records as a JUnit XML report with a test suite per day and a
+/// test case per part. Matching answers pass, differing ones fail, errors and
+/// timeouts are errors and answers without a stored one to check against are
+/// skipped.
+pub fn to_junit(records: &[Record]) -> String {
+    let count = |records: &[&Record], statuses: &[&str]| {
+        records
+            .iter()
+            .filter(|record| statuses.contains(&record.status))
+            .count()
+    };
+    let seconds = |records: &[&Record]| {
+        records
+            .iter()
+            .filter_map(|record| record.solve_time)
+            .sum::<Duration>()
+            .as_secs_f64()
+    };
+
+    let all = records.iter().collect::<Vec<_>>();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        xml,
+        "<testsuites name=\"Advent of Code 2024\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+        all.len(),
+        count(&all, &["differs"]),
+        count(&all, &["error", "timeout"]),
+        count(&all, &["new"]),
+        seconds(&all)
+    )
+    .expect("writing to a String");
+
+    for (day, group) in &records.iter().chunk_by(|record| record.day) {
+        let group = group.collect::<Vec<_>>();
+        writeln!(
+            xml,
+            "  <testsuite name=\"Day {:02}: {}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+            day,
+            xml_escape(group[0].title),
+            group.len(),
+            count(&group, &["differs"]),
+            count(&group, &["error", "timeout"]),
+            count(&group, &["new"]),
+            seconds(&group)
+        )
+        .expect("writing to a String");
+
+        for record in group {
+            let name = record.part.map_or("input".to_string(), |part| format!("part{}", part));
+            let time = record.solve_time.unwrap_or_default().as_secs_f64();
+            let open = format!("    <testcase classname=\"day{:02}\" name=\"{}\" time=\"{:.6}\"", day, name, time);
+
+            let body = match (record.status, record.answer, record.expected) {
+                ("differs", Some(actual), Some(expected)) => {
+                    let message = format!("expected {}, got {}", expected, actual);
+                    format!(
+                        "<failure message=\"{}\" type=\"WrongAnswer\">expected: {}\nactual: {}</failure>",
+                        message, expected, actual
+                    )
+                }
+                ("new", Some(actual), _) => format!("<skipped message=\"no stored answer, got {}\"/>", actual),
+                ("timeout", _, _) => {
+                    let error = xml_escape(record.error.as_deref().unwrap_or_default());
+                    format!("<error message=\"{}\" type=\"Timeout\"/>", error)
+                }
+                ("error", _, _) => {
+                    let error = xml_escape(record.error.as_deref().unwrap_or_default());
+                    format!("<error message=\"{}\">{}</error>", error, error)
+                }
+                _ => String::new(),
+            };
+
+            if body.is_empty() {
+                writeln!(xml, "{}/>", open)
+            } else {
+                writeln!(xml, "{}>\n      {}\n    </testcase>", open, body)
+            }
+            .expect("writing to a String");
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Renders the records as a Markdown table with a row per day, linking to the
+/// solution and earning a star for every answer that matches the stored one.
+pub fn to_markdown(records: &[Record]) -> String {
+    let mut markdown = String::from("| Day | Title | Part 1 | Time | Part 2 | Time | Stars |\n");
+    markdown.push_str("|----:|-------|-------:|-----:|-------:|-----:|-------|\n");
+
+    for (day, group) in &records.iter().chunk_by(|record| record.day) {
+        let group = group.collect::<Vec<_>>();
+        let mut row = vec![
+            format!("[{}](src/days/day{:02}.rs)", day, day),
+            group[0].title.replace('|', "\\|"),
+        ];
+
+        for part in Part::ALL {
+            let (answer, time) = match group.iter().find(|record| record.part == Some(part)) {
+                Some(record) => (
+                    match (record.answer, record.status) {
+                        (Some(answer), _) => answer.to_string(),
+                        (None, "timeout") => "TIMEOUT".to_string(),
+                        (None, _) => "error".to_string(),
+                    },
+                    record.solve_time.map_or("-".to_string(), |time| format!("{:.2?}", time)),
+                ),
+                None => match group.iter().find(|record| record.part.is_none()) {
+                    Some(record) if record.status == "timeout" => ("TIMEOUT".to_string(), "-".to_string()),
+                    Some(_) => ("error".to_string(), "-".to_string()),
+                    None => ("-".to_string(), "-".to_string()),
+                },
+            };
+            row.push(answer);
+            row.push(time);
+        }
+
+        let stars = group.iter().filter(|record| record.status == "matches").count();
+        row.push("⭐".repeat(stars));
+        writeln!(markdown, "| {} |", row.join(" | ")).expect("writing to a String");
+    }
+
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{PartReport, Timeout};
+
+    fn reports() -> Vec<DayReport> {
+        let part = |part, answer: Result<usize>, status| PartReport {
+            part,
+            answer,
+            status,
+            elapsed: Duration::from_micros(2),
+            stats: None,
+            allocs: None,
+        };
+
+        vec![
+            DayReport {
+                day: 5,
+                title: "Print Queue",
+                input_hash: Some(1),
+                read_time: Some(Duration::from_micros(1)),
+                parse_time: Some(Duration::from_micros(3)),
+                parse_stats: None,
+                error: None,
+                parts: vec![
+                    part(Part::One, Ok(143), Some(AnswerStatus::Differs { expected: 140 })),
+                    part(Part::Two, Err(anyhow!("no \"middle\", page")), None),
+                ],
+            },
+            DayReport {
+                day: 7,
+                title: "Bridge Repair",
+                input_hash: None,
+                read_time: None,
+                parse_time: None,
+                parse_stats: None,
+                error: Some(anyhow!("input not found")),
+                parts: Vec::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_formats() {
+        for format in [Format::Text, Format::Json, Format::Csv, Format::Junit, Format::Markdown] {
+            assert_eq!(format, format.to_string().parse().unwrap());
+        }
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = to_json(&records(&reports()));
+        let lines = json.lines().collect::<Vec<_>>();
+
+        assert_eq!(5, lines.len());
+        assert_eq!(
+            "  {\"day\": 5, \"title\": \"Print Queue\", \"part\": 1, \"answer\": 143, \"status\": \"differs\", \
+             \"expected\": 140, \"parse_ns\": 3000, \"solve_ns\": 2000, \"error\": null},",
+            lines[1]
+        );
+        assert!(lines[2].contains("\"answer\": null, \"status\": \"error\""));
+        assert!(lines[2].contains("\"error\": \"no \\\"middle\\\", page\""));
+        assert!(lines[3].contains("\"part\": null"));
+        assert_eq!("[]\n", to_json(&[]));
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = to_csv(&records(&reports()));
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!("day,title,part,answer,status,expected,parse_ns,solve_ns,error", lines[0]);
+        assert_eq!("5,Print Queue,1,143,differs,140,3000,2000,", lines[1]);
+        assert_eq!("5,Print Queue,2,,error,,3000,2000,\"no \"\"middle\"\", page\"", lines[2]);
+        assert_eq!("7,Bridge Repair,,,error,,,,input not found", lines[3]);
+    }
+
+    #[test]
+    fn renders_junit() {
+        let xml = to_junit(&records(&reports()));
+
+        assert!(xml.contains("<testsuites name=\"Advent of Code 2024\" tests=\"3\" failures=\"1\" errors=\"2\" skipped=\"0\""));
+        assert!(xml.contains("<testsuite name=\"Day 05: Print Queue\" tests=\"2\" failures=\"1\" errors=\"1\""));
+        assert!(xml.contains(
+            "<testcase classname=\"day05\" name=\"part1\" time=\"0.000002\">\n      \
+             <failure message=\"expected 140, got 143\" type=\"WrongAnswer\">expected: 140\nactual: 143</failure>"
+        ));
+        assert!(xml.contains("<error message=\"no &quot;middle&quot;, page\">"));
+        assert!(xml.contains("<testcase classname=\"day07\" name=\"input\" time=\"0.000000\">"));
+        assert!(xml.ends_with("  </testsuite>\n</testsuites>\n"));
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let mut records = records(&reports());
+        records[0].status = "matches";
+        let markdown = to_markdown(&records);
+        let lines = markdown.lines().collect::<Vec<_>>();
+
+        assert_eq!("| Day | Title | Part 1 | Time | Part 2 | Time | Stars |", lines[0]);
+        assert_eq!("| [5](src/days/day05.rs) | Print Queue | 143 | 2.00µs | error | 2.00µs | ⭐ |", lines[2]);
+        assert_eq!("| [7](src/days/day07.rs) | Bridge Repair | error | - | error | - |  |", lines[3]);
+    }
+
+    #[test]
+    fn marks_parse_timeouts() {
+        let report = DayReport {
+            day: 6,
+            title: "Guard Gallivant",
+            input_hash: Some(1),
+            read_time: Some(Duration::from_micros(1)),
+            parse_time: Some(Duration::from_millis(10)),
+            parse_stats: None,
+            error: Some(Timeout(Duration::from_millis(10)).into()),
+            parts: Vec::new(),
+        };
+        let records = records(&[report]);
+
+        assert_eq!("6,Guard Gallivant,,,timeout,,10000000,,TIMEOUT after 10.00ms", to_csv(&records).lines().nth(1).unwrap());
+        assert!(to_junit(&records).contains("<error message=\"TIMEOUT after 10.00ms\" type=\"Timeout\"/>"));
+        assert!(to_markdown(&records).contains("| Guard Gallivant | TIMEOUT | - | TIMEOUT | - |  |"));
+    }
+}
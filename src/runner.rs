@@ -0,0 +1,876 @@
+use anyhow::*;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::alloc::{self, AllocStats};
+use crate::answers::{self, input_hash, AnswerStatus, AnswerStore};
+use crate::bench::{self, BenchConfig, Stats};
+use crate::history::{self, History, Step};
+use crate::input::InputSource;
+use crate::report::{self, Format};
+use crate::pool;
+use crate::solution::{DynSolution, ParsedInput, Part};
+use crate::start_day;
+
+/// Settings shared by the `aoc` runner and the per-day binaries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Only run this part, both parts when `None`.
+    pub part: Option<Part>,
+    pub input: InputSource,
+    /// Answers file to verify against, [`answers::DEFAULT_PATH`] when `None`.
+    pub answers: Option<PathBuf>,
+    /// Store this run's answers as the accepted ones.
+    pub record: bool,
+    /// Time every part over many runs instead of once.
+    pub bench: Option<BenchConfig>,
+    /// Benchmark history to append to, [`history::DEFAULT_PATH`] when `None`.
+    pub history: Option<PathBuf>,
+    pub format: Format,
+    /// Give up on a part that runs longer than this.
+    pub timeout: Option<Duration>,
+    /// Number of worker threads, see [`Options::threads`] for the default.
+    pub jobs: Option<usize>,
+}
+
+impl Options {
+    /// Consumes the flags it knows, returning the remaining arguments in order.
+    pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<(Self, Vec<String>)> {
+        let mut options = Options::default();
+        let mut rest = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+                _ => (arg.clone(), None),
+            };
+
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .with_context(|| format!("missing value for {}", flag))
+            };
+
+            match flag.as_str() {
+                "--part" => options.part = Some(value()?.parse()?),
+                "--input" => options.input = InputSource::from_arg(&value()?),
+                "--answers" => options.answers = Some(PathBuf::from(value()?)),
+                "--record" => options.record = true,
+                "--history" => options.history = Some(PathBuf::from(value()?)),
+                "--format" => options.format = value()?.parse()?,
+                "--timeout" => options.timeout = Some(parse_duration(&value()?)?),
+                "--jobs" => options.jobs = Some(number(value()?)?),
+                "--serial" => options.jobs = Some(1),
+                "--bench" => {
+                    options.bench.get_or_insert_with(BenchConfig::default);
+                }
+                "--warmup" => options.bench.get_or_insert_with(BenchConfig::default).warmup = number(value()?)?,
+                "--iterations" => {
+                    options.bench.get_or_insert_with(BenchConfig::default).iterations = number(value()?)?
+                }
+                _ => rest.push(arg),
+            }
+        }
+
+        ensure!(
+            options.bench.is_none_or(|bench| bench.iterations > 0),
+            "--iterations must be at least 1"
+        );
+        ensure!(options.jobs != Some(0), "--jobs must be at least 1");
+
+        Ok((options, rest))
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.answers
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(answers::DEFAULT_PATH))
+    }
+
+    pub fn history_path(&self) -> PathBuf {
+        self.history
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(history::DEFAULT_PATH))
+    }
+
+    /// Number of worker threads to run days and parts on: `--jobs` if given,
+    /// otherwise a single one when benchmarking so that parts running at the
+    /// same time do not skew the timings kept in the history, and one per
+    /// available core else.
+    pub fn threads(&self) -> usize {
+        match (self.jobs, &self.bench) {
+            (Some(jobs), _) => jobs,
+            (None, Some(_)) => 1,
+            (None, None) => thread::available_parallelism().map_or(1, |threads| threads.get()),
+        }
+    }
+
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Result<usize>,
+    /// Comparison with the stored answer, `None` if the part failed.
+    pub status: Option<AnswerStatus>,
+    /// Solve time, the median when benchmarking.
+    pub elapsed: Duration,
+    /// Timing statistics, only when benchmarking.
+    pub stats: Option<Stats>,
+    /// Heap usage of a single run, only with the `alloc-stats` feature.
+    pub allocs: Option<AllocStats>,
+}
+
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: u8,
+    pub title: &'static str,
+    pub input_hash: Option<u64>,
+    /// Time to read the input, `None` if that failed.
+    pub read_time: Option<Duration>,
+    /// Time to parse the input, the median when benchmarking, `None` if it
+    /// could not be read or parsed.
+    pub parse_time: Option<Duration>,
+    /// Parse timing statistics, only when benchmarking.
+    pub parse_stats: Option<Stats>,
+    /// Set when the input could not be read or parsed, no part ran in that case.
+    pub error: Option<Error>,
+    pub parts: Vec<PartReport>,
+}
+
+/// Error of parsing or a part that did not finish within the time limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout(pub Duration);
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TIMEOUT after {:.2?}", self.0)
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+impl PartReport {
+    pub fn timed_out(&self) -> bool {
+        matches!(&self.answer, Err(err) if err.is::<Timeout>())
+    }
+}
+
+impl DayReport {
+    /// Whether parsing the input ran into the time limit.
+    pub fn timed_out(&self) -> bool {
+        matches!(&self.error, Some(err) if err.is::<Timeout>())
+    }
+
+    /// Whether every part produced an answer that does not contradict the stored one.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+            && self.parts.iter().all(|part| {
+                part.answer.is_ok() && !matches!(part.status, Some(AnswerStatus::Differs { .. }))
+            })
+    }
+
+    pub fn part(&self, part: Part) -> Option<&PartReport> {
+        self.parts.iter().find(|report| report.part == part)
+    }
+
+    /// Turns a failed day into an error, the details have already been printed.
+    pub fn into_result(self) -> Result<()> {
+        ensure!(self.is_ok(), "Day {:02} failed", self.day);
+        Ok(())
+    }
+}
+
+/// Parses a duration such as `10`, `2.5s`, `500ms` or `1m`, plain numbers being seconds.
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let (number, unit) = match value.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(idx) => value.split_at(idx),
+        None => (value, "s"),
+    };
+
+    let scale = match unit {
+        "ms" => 0.001,
+        "s" => 1.0,
+        "m" => 60.0,
+        _ => bail!("invalid unit in duration '{}', expected ms, s or m", value),
+    };
+    let number = number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite() && *number > 0.0)
+        .with_context(|| format!("invalid duration '{}', expected a positive number", value))?;
+
+    Ok(Duration::from_secs_f64(number * scale))
+}
+
+fn number(value: String) -> Result<usize> {
+    value
+        .parse()
+        .with_context(|| format!("invalid count '{}', expected a number", value))
+}
+
+/// Parses a day selection such as `5`, `1-8` or `1,3,5-7`.
+pub fn parse_days(spec: &str) -> Result<Vec<u8>> {
+    let mut days = Vec::new();
+
+    for item in spec.split(',') {
+        let (from, to) = match item.split_once('-') {
+            Some((from, to)) => (from, to),
+            None => (item, item),
+        };
+
+        let parse = |day: &str| {
+            day.trim()
+                .parse::<u8>()
+                .ok()
+                .filter(|day| (1..=25).contains(day))
+                .with_context(|| format!("invalid day '{}' in '{}', expected 1 to 25", day, spec))
+        };
+
+        let (from, to) = (parse(from)?, parse(to)?);
+        ensure!(from <= to, "invalid day range '{}'", item);
+
+        for day in from..=to {
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+    }
+
+    Ok(days)
+}
+
+/// Runs `f` on a worker thread, giving up on it after `limit`. A step that
+/// timed out cannot be stopped, its thread keeps running in the background
+/// until the process exits.
+fn run_with_timeout<T, F>(limit: Duration, f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name("part".to_string())
+        .spawn(move || {
+            // the receiver is gone if we timed out, nobody wants the result then
+            let _ = sender.send(f());
+        })
+        .context("failed to spawn a worker thread")?;
+
+    match receiver.recv_timeout(limit) {
+        Result::Ok(result) => Ok(result),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(Timeout(limit).into()),
+        Err(mpsc::RecvTimeoutError::Disconnected) => bail!("panicked"),
+    }
+}
+
+/// Runs `f`, turning a panic into an error carrying the panic message so
+/// that one broken day does not take down the whole run.
+fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "no message".to_string());
+        Err(anyhow!("panicked: {}", message))
+    })
+}
+
+/// Reads and parses a day's input, returning the report without any parts
+/// and the parsed input if that worked.
+fn prepare(solution: &'static dyn DynSolution, options: &Options) -> (DayReport, Option<Arc<ParsedInput>>) {
+    let mut report = DayReport {
+        day: solution.day(),
+        title: solution.title(),
+        input_hash: None,
+        read_time: None,
+        parse_time: None,
+        parse_stats: None,
+        error: None,
+        parts: Vec::new(),
+    };
+
+    let start = Instant::now();
+    let raw = match options.input.read(solution.day()) {
+        Result::Ok(raw) => raw,
+        Err(err) => {
+            report.error = Some(err);
+            return (report, None);
+        }
+    };
+    report.read_time = Some(start.elapsed());
+    report.input_hash = Some(input_hash(&raw));
+
+    let bench = options.bench;
+    let job = move || match &bench {
+        Some(config) => {
+            bench::bench(config, || catch_panic(|| solution.parse(&raw)))
+                .map(|(input, stats)| (input, stats.median, Some(stats)))
+        }
+        None => {
+            let start = Instant::now();
+            catch_panic(|| solution.parse(&raw)).map(|input| (input, start.elapsed(), None))
+        }
+    };
+
+    let parsed = match options.timeout {
+        Some(limit) => run_with_timeout(limit, job).and_then(|parsed| parsed),
+        None => job(),
+    };
+    match parsed {
+        Result::Ok((input, elapsed, stats)) => {
+            report.parse_time = Some(elapsed);
+            report.parse_stats = stats;
+            (report, Some(Arc::new(input)))
+        }
+        Err(err) => {
+            if err.is::<Timeout>() {
+                report.parse_time = options.timeout;
+            }
+            report.error = Some(err);
+            (report, None)
+        }
+    }
+}
+
+/// Solves one part of a day and checks the answer against the store.
+fn solve_part(
+    solution: &'static dyn DynSolution,
+    input: &Arc<ParsedInput>,
+    input_hash: Option<u64>,
+    part: Part,
+    options: &Options,
+    store: &AnswerStore,
+) -> PartReport {
+    let job = {
+        let (input, bench) = (Arc::clone(input), options.bench);
+        move || {
+            alloc::measure(|| match &bench {
+                Some(config) => match bench::bench(config, || catch_panic(|| solution.solve(&input, part))) {
+                    Result::Ok((answer, stats)) => (Ok(answer), stats.median, Some(stats)),
+                    Err(err) => (Err(err), Duration::ZERO, None),
+                },
+                None => {
+                    let start = Instant::now();
+                    let answer = catch_panic(|| solution.solve(&input, part));
+                    (answer, start.elapsed(), None)
+                }
+            })
+        }
+    };
+
+    let start = Instant::now();
+    let ((answer, elapsed, stats), allocs) = match options.timeout {
+        Some(limit) => run_with_timeout(limit, job).unwrap_or_else(|err| {
+            // only a part that timed out took the whole limit
+            let elapsed = if err.is::<Timeout>() { limit } else { start.elapsed() };
+            ((Err(err), elapsed, None), None)
+        }),
+        None => job(),
+    };
+    let allocs = allocs.map(|allocs| allocs.per_run(options.bench.map_or(1, |config| config.runs())));
+
+    let status = answer
+        .as_ref()
+        .ok()
+        .zip(input_hash)
+        .map(|(&answer, hash)| store.check(solution.day(), hash, part, answer));
+
+    PartReport {
+        part,
+        answer,
+        status,
+        elapsed,
+        stats,
+        allocs,
+    }
+}
+
+/// Runs one day against the configured input and checks each part's answer
+/// against the store, without printing anything.
+pub fn run_one(solution: &'static dyn DynSolution, options: &Options, store: &AnswerStore) -> DayReport {
+    let (mut report, input) = prepare(solution, options);
+
+    if let Some(input) = input {
+        for part in options.parts() {
+            report
+                .parts
+                .push(solve_part(solution, &input, report.input_hash, part, options, store));
+        }
+    }
+
+    report
+}
+
+/// A day's input, read and parsed by whichever of its parts runs first.
+struct Prepared {
+    /// Taken once all parts are done.
+    report: Mutex<Option<DayReport>>,
+    input: Option<Arc<ParsedInput>>,
+    input_hash: Option<u64>,
+}
+
+/// Runs every part of every day on `threads` worker threads, handing the
+/// finished days to `finish` in order.
+fn run_parallel(
+    solutions: &[&'static dyn DynSolution],
+    options: &Options,
+    store: &AnswerStore,
+    threads: usize,
+    mut finish: impl FnMut(DayReport),
+) {
+    let parts = options.parts();
+    let jobs = (0..solutions.len())
+        .flat_map(|idx| parts.iter().map(move |&part| (idx, part)))
+        .collect::<Vec<_>>();
+    let prepared = solutions.iter().map(|_| OnceLock::new()).collect::<Vec<_>>();
+    let mut done = 0;
+
+    let prepare = |idx: usize| -> &Prepared {
+        prepared[idx].get_or_init(|| {
+            let (report, input) = prepare(solutions[idx], options);
+            Prepared {
+                input_hash: report.input_hash,
+                report: Mutex::new(Some(report)),
+                input,
+            }
+        })
+    };
+
+    pool::for_each_ordered(
+        threads,
+        &jobs,
+        |&(idx, part)| {
+            let day = prepare(idx);
+            let report = day
+                .input
+                .as_ref()
+                .map(|input| solve_part(solutions[idx], input, day.input_hash, part, options, store));
+            (idx, report)
+        },
+        |(idx, part)| {
+            // results arrive in order, so a day is done once all its parts are in
+            let mut report = prepare(idx).report.lock().expect("day report lock poisoned");
+            let day = report.as_mut().expect("day already finished");
+            if let Some(part) = part {
+                day.parts.push(part);
+            }
+
+            done += 1;
+            if done == parts.len() {
+                done = 0;
+                finish(report.take().expect("day already finished"));
+            }
+        },
+    );
+}
+
+/// Prints how long a step took, with the statistics when benchmarking.
+fn print_time(what: &str, elapsed: Duration, stats: Option<&Stats>) {
+    match stats {
+        Some(stats) => {
+            println!("{} {:.2?} (median of {} runs).", what, elapsed, stats.samples);
+            println!("{}", stats);
+        }
+        None => println!("{} {:.2?}.", what, elapsed),
+    }
+}
+
+/// Prints the banner, the read and parse times and each part's answer checked
+/// against the store.
+pub fn print_day(report: &DayReport) {
+    start_day(&report.day.to_string());
+
+    if let Some(read_time) = report.read_time {
+        print_time("Read input in", read_time, None);
+    }
+    if let Some(parse_time) = report.parse_time {
+        print_time("Parsed input in", parse_time, report.parse_stats.as_ref());
+    }
+    match &report.error {
+        Some(err) if report.timed_out() => println!("Parsing input: {}", err),
+        Some(err) => println!("Error: {:#}", err),
+        None => {}
+    }
+
+    for part in &report.parts {
+        println!();
+        println!("=== Part {} ===", part.part);
+
+        print_time("Took", part.elapsed, part.stats.as_ref());
+        if let Some(allocs) = part.allocs {
+            println!("Heap: {}.", allocs);
+        }
+        match (&part.answer, part.status) {
+            (Result::Ok(answer), Some(status)) => println!("Result = {} {}", answer, status),
+            (Result::Ok(answer), None) => println!("Result = {}", answer),
+            (Err(err), _) if part.timed_out() => println!("{}", err),
+            (Err(err), _) => println!("Error: {:#}", err),
+        }
+    }
+}
+
+/// Runs the given days, in parallel unless there is a single worker thread,
+/// records the answers if asked to and appends benchmark results to the
+/// history. Text output is printed day by day in order, separated by blank
+/// lines, structured formats once all days ran.
+pub fn run(solutions: &[&'static dyn DynSolution], options: &Options) -> Result<Vec<DayReport>> {
+    let mut store = AnswerStore::load(options.answers_path())?;
+    let mut reports = Vec::with_capacity(solutions.len());
+
+    let mut finish = |report: DayReport| {
+        if options.format == Format::Text {
+            if !reports.is_empty() {
+                println!();
+            }
+            print_day(&report);
+        }
+        reports.push(report);
+    };
+
+    match options.threads() {
+        1 => solutions
+            .iter()
+            .for_each(|solution| finish(run_one(*solution, options, &store))),
+        threads => run_parallel(solutions, options, &store, threads, finish),
+    }
+
+    if options.record {
+        for report in &reports {
+            record(&mut store, report);
+        }
+
+        store.save()?;
+        note(options, &format!("Recorded answers in {}", store.path().display()));
+    }
+
+    if options.bench.is_some() {
+        let mut history = History::load(options.history_path())?;
+        let (timestamp, rev) = (history::timestamp(), history::git_rev());
+
+        let mut entries = Vec::new();
+        for report in &reports {
+            let steps = std::iter::once((Step::Parse, report.parse_stats))
+                .chain(report.parts.iter().map(|part| (Step::Part(part.part), part.stats)));
+            for (step, stats) in steps {
+                if let (Some(input_hash), Some(stats)) = (report.input_hash, stats) {
+                    entries.push(history::Entry {
+                        timestamp,
+                        rev: rev.clone(),
+                        day: report.day,
+                        step,
+                        input_hash,
+                        stats,
+                    });
+                }
+            }
+        }
+
+        if !entries.is_empty() {
+            history.append(&entries)?;
+            note(options, &format!("Recorded timings in {}", history.path().display()));
+        }
+    }
+
+    match options.format {
+        Format::Text => {}
+        Format::Json => print!("{}", report::to_json(&report::records(&reports))),
+        Format::Csv => print!("{}", report::to_csv(&report::records(&reports))),
+        Format::Junit => print!("{}", report::to_junit(&report::records(&reports))),
+        Format::Markdown => print!("{}", report::to_markdown(&report::records(&reports))),
+    }
+
+    Ok(reports)
+}
+
+/// Prints a message after the days' output, on stderr for structured formats
+/// so that stdout stays parseable.
+fn note(options: &Options, message: &str) {
+    match options.format {
+        Format::Text => println!("\n{}", message),
+        _ => eprintln!("{}", message),
+    }
+}
+
+/// Stores every answer of the report as the accepted one.
+pub fn record(store: &mut AnswerStore, report: &DayReport) {
+    let Some(hash) = report.input_hash else {
+        return;
+    };
+
+    for part in &report.parts {
+        if let Result::Ok(answer) = part.answer {
+            store.set(report.day, hash, part.part, answer);
+        }
+    }
+}
+
+/// Prints one row per day with the read and parse times and each part's
+/// answer and solve time.
+pub fn print_summary(reports: &[DayReport]) {
+    let mut rows = vec![vec![
+        "Day".to_string(),
+        "Title".to_string(),
+        "Read".to_string(),
+        "Parse".to_string(),
+        "Part 1".to_string(),
+        "Time".to_string(),
+        "Part 2".to_string(),
+        "Time".to_string(),
+    ]];
+
+    for report in reports {
+        let time = |time: Option<Duration>| time.map_or("-".to_string(), |time| format!("{:.2?}", time));
+        let mut row = vec![
+            format!("{:02}", report.day),
+            report.title.to_string(),
+            time(report.read_time),
+            match report.timed_out() {
+                true => "TIMEOUT".to_string(),
+                false => time(report.parse_time),
+            },
+        ];
+
+        for part in Part::ALL {
+            let (answer, time) = match (&report.error, report.part(part)) {
+                (Some(_), _) => ("error".to_string(), "-".to_string()),
+                (None, None) => ("-".to_string(), "-".to_string()),
+                (None, Some(part)) => match (&part.answer, part.status) {
+                    (Result::Ok(answer), Some(status)) => {
+                        (format!("{} {}", answer, status), format!("{:.2?}", part.elapsed))
+                    }
+                    (Result::Ok(answer), None) => (answer.to_string(), format!("{:.2?}", part.elapsed)),
+                    (Err(_), _) if part.timed_out() => ("TIMEOUT".to_string(), format!("{:.2?}", part.elapsed)),
+                    (Err(_), _) => ("error".to_string(), format!("{:.2?}", part.elapsed)),
+                },
+            };
+            row.push(answer);
+            row.push(time);
+        }
+
+        rows.push(row);
+    }
+
+    print_table("Summary", &rows);
+}
+
+/// Prints `rows` as left-aligned columns under a `=== title ===` heading, the
+/// first row being the column headers.
+pub fn print_table(title: &str, rows: &[Vec<String>]) {
+    let widths = (0..rows[0].len())
+        .map(|col| rows.iter().map(|row| row[col].chars().count()).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+
+    println!("\n=== {} ===", title);
+    for row in rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_day_specs() {
+        assert_eq!(vec![5], parse_days("5").unwrap());
+        assert_eq!((1..=8).collect::<Vec<_>>(), parse_days("1-8").unwrap());
+        assert_eq!(vec![1, 3, 5, 6, 7], parse_days("1,3,5-7,6").unwrap());
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("8-1").is_err());
+        assert!(parse_days("x").is_err());
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(Duration::from_secs(10), parse_duration("10").unwrap());
+        assert_eq!(Duration::from_millis(2500), parse_duration("2.5s").unwrap());
+        assert_eq!(Duration::from_millis(500), parse_duration("500ms").unwrap());
+        assert_eq!(Duration::from_secs(60), parse_duration("1m").unwrap());
+        assert!(parse_duration("0").is_err());
+        assert!(parse_duration("5h").is_err());
+        assert!(parse_duration("s").is_err());
+    }
+
+    #[test]
+    fn times_out_slow_parts() {
+        assert_eq!(3, run_with_timeout(Duration::from_secs(10), || 3).unwrap());
+
+        let err = run_with_timeout(Duration::from_millis(10), || thread::sleep(Duration::from_secs(1))).unwrap_err();
+        assert!(err.is::<Timeout>());
+        assert_eq!("TIMEOUT after 10.00ms", err.to_string());
+    }
+
+    struct Panicking;
+
+    impl crate::Solution for Panicking {
+        const DAY: u8 = 25;
+        const TITLE: &'static str = "Panicking";
+
+        type Input = usize;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.len())
+        }
+
+        fn part1(_input: &Self::Input) -> Result<usize> {
+            panic!("Graph contains a cycle!")
+        }
+
+        fn part2(input: &Self::Input) -> Result<usize> {
+            Ok(*input)
+        }
+    }
+
+    #[test]
+    fn contains_panicking_parts() {
+        use crate::solution::Registered;
+
+        static PANICKING: Registered<Panicking> = Registered::new();
+        let solutions: [&'static dyn DynSolution; 2] = [&PANICKING, crate::days::DAYS[0]];
+        let input = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/01-1.txt");
+        let options = |timeout| Options {
+            input: InputSource::Path(input.clone()),
+            timeout,
+            ..Options::default()
+        };
+        let store = AnswerStore::default();
+
+        for timeout in [None, Some(Duration::from_secs(5))] {
+            let report = run_one(solutions[0], &options(timeout), &store);
+            let part1 = report.part(Part::One).unwrap();
+            let err = part1.answer.as_ref().unwrap_err();
+            assert_eq!("panicked: Graph contains a cycle!", err.to_string());
+            assert!(!part1.timed_out());
+            assert!(part1.elapsed < Duration::from_secs(1));
+            assert!(report.part(Part::Two).unwrap().answer.is_ok());
+            assert!(!report.is_ok());
+        }
+
+        // the other days still run, in parallel as well
+        let mut reports = Vec::new();
+        run_parallel(&solutions, &options(None), &store, 4, |report| reports.push(report));
+        assert_eq!(vec![25, 1], reports.iter().map(|report| report.day).collect::<Vec<_>>());
+        assert!(reports[1].is_ok());
+        assert_eq!(11, *reports[1].part(Part::One).unwrap().answer.as_ref().unwrap());
+    }
+
+    struct SlowParse;
+
+    impl crate::Solution for SlowParse {
+        const DAY: u8 = 24;
+        const TITLE: &'static str = "Slow Parse";
+
+        type Input = usize;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            thread::sleep(Duration::from_secs(1));
+            Ok(input.len())
+        }
+
+        fn part1(input: &Self::Input) -> Result<usize> {
+            Ok(*input)
+        }
+
+        fn part2(input: &Self::Input) -> Result<usize> {
+            Ok(*input)
+        }
+    }
+
+    #[test]
+    fn times_out_slow_parsing() {
+        use crate::solution::Registered;
+
+        static SLOW_PARSE: Registered<SlowParse> = Registered::new();
+        let options = Options {
+            input: InputSource::Path(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/01-1.txt")),
+            timeout: Some(Duration::from_millis(10)),
+            ..Options::default()
+        };
+
+        let start = Instant::now();
+        let report = run_one(&SLOW_PARSE, &options, &AnswerStore::default());
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert!(report.timed_out());
+        assert_eq!(Some(Duration::from_millis(10)), report.parse_time);
+        assert_eq!("TIMEOUT after 10.00ms", report.error.as_ref().unwrap().to_string());
+        assert!(report.parts.is_empty());
+        assert!(!report.is_ok());
+    }
+
+    #[test]
+    fn parses_options() {
+        let (options, rest) = Options::parse_args(args(&["5", "--part", "2"])).unwrap();
+        assert_eq!(Some(Part::Two), options.part);
+        assert_eq!(args(&["5"]), rest);
+
+        let (options, rest) = Options::parse_args(args(&["--part=1", "--all"])).unwrap();
+        assert_eq!(vec![Part::One], options.parts());
+        assert_eq!(args(&["--all"]), rest);
+
+        let (options, _) = Options::parse_args(args(&["--input", "-"])).unwrap();
+        assert_eq!(InputSource::Stdin, options.input);
+        assert!(!options.record);
+
+        let (options, _) = Options::parse_args(args(&["--record", "--answers", "a.toml"])).unwrap();
+        assert!(options.record);
+        assert_eq!(PathBuf::from("a.toml"), options.answers_path());
+
+        let (options, _) = Options::parse_args(args(&["--bench"])).unwrap();
+        assert_eq!(Some(BenchConfig::default()), options.bench);
+        assert_eq!(PathBuf::from(history::DEFAULT_PATH), options.history_path());
+        assert_eq!(Format::Text, options.format);
+
+        let (options, _) = Options::parse_args(args(&["--format", "json"])).unwrap();
+        assert_eq!(Format::Json, options.format);
+        assert!(Options::parse_args(args(&["--format=xml"])).is_err());
+
+        let (options, _) = Options::parse_args(args(&["--iterations=50", "--warmup", "0"])).unwrap();
+        assert_eq!(Some(BenchConfig { warmup: 0, iterations: 50 }), options.bench);
+
+        assert!(Options::parse_args(args(&["--iterations", "0"])).is_err());
+        assert!(Options::parse_args(args(&["--warmup", "x"])).is_err());
+        let (options, _) = Options::parse_args(args(&["--timeout=500ms"])).unwrap();
+        assert_eq!(Some(Duration::from_millis(500)), options.timeout);
+        assert!(options.threads() >= 1);
+
+        let (options, _) = Options::parse_args(args(&["--jobs", "4"])).unwrap();
+        assert_eq!(4, options.threads());
+        let (options, _) = Options::parse_args(args(&["--serial"])).unwrap();
+        assert_eq!(1, options.threads());
+        let (options, _) = Options::parse_args(args(&["--bench"])).unwrap();
+        assert_eq!(1, options.threads());
+        let (options, _) = Options::parse_args(args(&["--iterations", "5"])).unwrap();
+        assert_eq!(1, options.threads());
+        let (options, _) = Options::parse_args(args(&["--jobs", "4", "--bench"])).unwrap();
+        assert_eq!(4, options.threads());
+        assert!(Options::parse_args(args(&["--jobs=0"])).is_err());
+
+        assert!(Options::parse_args(args(&["--part", "3"])).is_err());
+        assert!(Options::parse_args(args(&["--part"])).is_err());
+    }
+}
@@ -0,0 +1,324 @@
+use anyhow::*;
+use regex::Regex;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::examples::{self, EXAMPLES_DIR};
+use crate::puzzle;
+
+/// Template new days are generated from, relative to the crate root.
+pub const TEMPLATE: &str = "src/bin/NN.rs";
+
+/// The closest directory from `start` up that has a `Cargo.toml`.
+pub fn find_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// The crate being worked on, found from the current directory rather than
+/// where the binary was built, so that a shared target directory or a second
+/// checkout never makes a command touch another checkout's files.
+pub fn crate_root() -> Result<PathBuf> {
+    let dir = env::current_dir().context("failed to get the current directory")?;
+    find_root(&dir).with_context(|| format!("found no Cargo.toml in {} or above it", dir.display()))
+}
+
+/// Title of `day` from its archived puzzle description in the crate at
+/// `root`, if there is one.
+pub fn archived_title(root: &Path, day: u8) -> Option<String> {
+    let markdown = fs::read_to_string(root.join(puzzle::default_path(day))).ok()?;
+    let heading = markdown.lines().next()?.strip_prefix("## Day ")?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim().to_string())
+}
+
+/// The solution module of `day` made from the `NN.rs` template: the day and
+/// `title` filled in, using the crate's own items and without `main`.
+pub fn day_source(template: &str, day: u8, title: Option<&str>) -> Result<String> {
+    let day_line = "const DAY: u8 = 0; // TODO: Fill the day";
+    let title_line = r#"const TITLE: &'static str = "<TITLE>"; // TODO: Fill the puzzle title"#;
+    for expected in ["use adv_code_2024::*;", day_line, title_line, "\nfn main()"] {
+        ensure!(template.contains(expected), "the template {} has no '{}'", TEMPLATE, expected.trim());
+    }
+
+    let (solution, _) = template.split_once("\nfn main()").unwrap_or((template, ""));
+    let mut source = solution
+        .replace("use adv_code_2024::*;", "use crate::Solution;")
+        .replace(day_line, &format!("const DAY: u8 = {};", day));
+    if let Some(title) = title {
+        let title = title.replace('\\', "\\\\").replace('"', "\\\"");
+        source = source.replace(title_line, &format!("const TITLE: &'static str = \"{}\";", title));
+    }
+
+    let mut source = source.replace("NN", &format!("{:02}", day)).trim_end().to_string();
+    source.push('\n');
+    Ok(source)
+}
+
+/// The binary running `day` on its own.
+pub fn bin_source(day: u8) -> String {
+    format!(
+        "use anyhow::*;\n\
+         use adv_code_2024::*;\n\
+         use adv_code_2024::days::day{0:02}::Day{0:02};\n\
+         \n\
+         fn main() -> Result<()> {{\n    \
+             run_day::<Day{0:02}>()\n\
+         }}\n",
+        day
+    )
+}
+
+/// Inserts `block` into `lines` among the blocks whose first line matches
+/// `pattern`, keeping them ordered by the day the pattern captures. A block
+/// spans `before` lines above its matching line and `after` lines below, and
+/// is separated from its neighbour by a blank line if `blank` is set.
+fn insert_ordered(
+    lines: &mut Vec<String>,
+    pattern: &str,
+    day: u8,
+    block: &[String],
+    (before, after): (usize, usize),
+    blank: bool,
+) -> Result<()> {
+    let pattern = Regex::new(pattern).expect("valid regex");
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| Some((idx, pattern.captures(line)?[1].parse::<u8>().ok()?)))
+        .collect::<Vec<_>>();
+    ensure!(!days.is_empty(), "found no line like '{}'", pattern);
+    ensure!(days.iter().all(|&(_, other)| other != day), "Day {:02} is registered already", day);
+
+    let mut block = block.to_vec();
+    let at = match days.iter().rev().find(|&&(_, other)| other < day) {
+        Some(&(idx, _)) => {
+            if blank {
+                block.insert(0, String::new());
+            }
+            idx + after + 1
+        }
+        None => {
+            if blank {
+                block.push(String::new());
+            }
+            days[0].0 - before
+        }
+    };
+    lines.splice(at..at, block);
+    Ok(())
+}
+
+/// Registers `day` in the `days` module: its `mod`, the entry in `DAYS` and
+/// the test checking its examples.
+pub fn register(mod_rs: &str, day: u8) -> Result<String> {
+    let mut lines = mod_rs.lines().map(String::from).collect::<Vec<_>>();
+
+    insert_ordered(
+        &mut lines,
+        r"^pub mod day(\d+);$",
+        day,
+        &[format!("pub mod day{:02};", day)],
+        (0, 0),
+        false,
+    )?;
+    insert_ordered(
+        &mut lines,
+        r"^    &Registered::<day(\d+)::Day\d+>::new\(\),$",
+        day,
+        &[format!("    &Registered::<day{0:02}::Day{0:02}>::new(),", day)],
+        (0, 0),
+        false,
+    )?;
+    insert_ordered(
+        &mut lines,
+        r"^    fn day(\d+)\(\) \{$",
+        day,
+        &[
+            "    #[test]".to_string(),
+            format!("    fn day{:02}() {{", day),
+            format!("        assert_examples({});", day),
+            "    }".to_string(),
+        ],
+        (1, 2),
+        true,
+    )?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Ticks `day` in the README checklist, linking its solution. `None` if the
+/// checklist has no open entry for it.
+pub fn tick_readme(readme: &str, day: u8) -> Option<String> {
+    let open = format!("- [ ] Day {}", day);
+    let idx = readme.lines().position(|line| line.trim_end() == open)?;
+
+    let lines = readme
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| match line_idx == idx {
+            true => format!("- [x] Day {} - [Solution](src/days/day{:02}.rs)", day, day),
+            false => line.to_string(),
+        })
+        .collect::<Vec<_>>();
+    Some(lines.join("\n") + if readme.ends_with('\n') { "\n" } else { "" })
+}
+
+/// What [`create`] did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scaffold {
+    /// Files that were created or changed.
+    pub files: Vec<PathBuf>,
+    /// Whether the README checklist had an entry to tick.
+    pub ticked: bool,
+}
+
+/// Creates `day` in the crate at `root` from the template: the solution
+/// module, its binary and an empty example, and registers it in the `days`
+/// module and the README. Refuses to touch a day that exists already.
+pub fn create(root: &Path, day: u8, title: Option<&str>) -> Result<Scaffold> {
+    ensure!((1..=25).contains(&day), "there is no Day {:02}, expected 1 to 25", day);
+
+    let source = root.join(format!("src/days/day{:02}.rs", day));
+    let bin = root.join(format!("src/bin/{:02}.rs", day));
+    let mod_rs = root.join("src/days/mod.rs");
+    let readme = root.join("README.md");
+    let example = examples::example_path(root.join(EXAMPLES_DIR), day, "1");
+    for path in [&source, &bin] {
+        ensure!(!path.exists(), "Day {:02} exists already, {} is there", day, path.display());
+    }
+
+    let read = |path: &Path| fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()));
+    let template = read(&root.join(TEMPLATE))?;
+    let day_source = day_source(&template, day, title)?;
+    let registered = register(&read(&mod_rs)?, day).with_context(|| format!("failed to update {}", mod_rs.display()))?;
+    let ticked = tick_readme(&read(&readme)?, day);
+
+    let write = |path: &Path, text: &str| fs::write(path, text).with_context(|| format!("failed to write {}", path.display()));
+    write(&source, &day_source)?;
+    write(&bin, &bin_source(day))?;
+    write(&mod_rs, &registered)?;
+    let mut files = vec![source, bin, mod_rs];
+
+    if let Some(readme_text) = &ticked {
+        write(&readme, readme_text)?;
+        files.push(readme.clone());
+    }
+    if !example.exists() {
+        let dir = example.parent().expect("examples have a directory");
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        write(&example, "")?;
+        write(&examples::expected_path(&example), "# part1 = <answer>\n# part2 = <answer>\n")?;
+        files.push(example);
+    }
+
+    Ok(Scaffold {
+        files,
+        ticked: ticked.is_some(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ScratchDir;
+
+    const MOD_RS: &str = "\
+use crate::solution::{DynSolution, Registered};
+
+pub mod day01;
+pub mod day03;
+
+pub static DAYS: &[&dyn DynSolution] = &[
+    &Registered::<day01::Day01>::new(),
+    &Registered::<day03::Day03>::new(),
+];
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn day01() {
+        assert_examples(1);
+    }
+
+    #[test]
+    fn day03() {
+        assert_examples(3);
+    }
+}
+";
+
+    #[test]
+    fn registers_days_in_order() {
+        let registered = register(&register(MOD_RS, 2).unwrap(), 4).unwrap();
+        assert_eq!(
+            MOD_RS
+                .replace("pub mod day01;\n", "pub mod day01;\npub mod day02;\n")
+                .replace("pub mod day03;\n", "pub mod day03;\npub mod day04;\n")
+                .replace(
+                    "Day01>::new(),\n",
+                    "Day01>::new(),\n    &Registered::<day02::Day02>::new(),\n"
+                )
+                .replace(
+                    "Day03>::new(),\n",
+                    "Day03>::new(),\n    &Registered::<day04::Day04>::new(),\n"
+                )
+                .replace(
+                    "assert_examples(1);\n    }\n",
+                    "assert_examples(1);\n    }\n\n    #[test]\n    fn day02() {\n        assert_examples(2);\n    }\n"
+                )
+                .replace(
+                    "assert_examples(3);\n    }\n",
+                    "assert_examples(3);\n    }\n\n    #[test]\n    fn day04() {\n        assert_examples(4);\n    }\n"
+                ),
+            registered
+        );
+
+        let first = register(&MOD_RS.replace("day01", "day02").replace("Day01", "Day02"), 1).unwrap();
+        assert!(first.contains("pub mod day01;\npub mod day02;\n"));
+        assert!(first.contains("    #[test]\n    fn day01() {\n        assert_examples(1);\n    }\n\n    #[test]\n"));
+
+        assert!(register(MOD_RS, 3).is_err());
+    }
+
+    #[test]
+    fn fills_the_template() {
+        let source = day_source(include_str!("bin/NN.rs"), 9, Some("Disk \"Fragmenter\"")).unwrap();
+        assert!(source.starts_with("use anyhow::*;\nuse crate::Solution;\n\npub struct Day09;\n"));
+        assert!(source.contains("    const DAY: u8 = 9;\n"));
+        assert!(source.contains("    const TITLE: &'static str = \"Disk \\\"Fragmenter\\\"\";\n"));
+        assert!(source.contains("examples/09-1.txt"));
+        assert!(!source.contains("fn main"));
+
+        let source = day_source(include_str!("bin/NN.rs"), 9, None).unwrap();
+        assert!(source.contains("\"<TITLE>\"; // TODO"));
+        assert!(day_source("fn main() {}", 9, None).is_err());
+    }
+
+    #[test]
+    fn finds_the_crate_root() {
+        let root = ScratchDir::new("scaffold");
+        let nested = root.join("src/days");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join("Cargo.toml"), "[package]\n").unwrap();
+        assert_eq!(Some(root.path().to_path_buf()), find_root(&nested));
+        assert_eq!(Some(root.path().to_path_buf()), find_root(root.path()));
+
+        fs::create_dir_all(root.join("puzzles")).unwrap();
+        fs::write(root.join("puzzles/09.md"), "## Day 9: Disk Fragmenter\n\nText\n").unwrap();
+        assert_eq!(Some("Disk Fragmenter".to_string()), archived_title(root.path(), 9));
+        assert_eq!(None, archived_title(root.path(), 10));
+    }
+
+    #[test]
+    fn ticks_the_readme() {
+        let readme = "- [x] Day 8 - [Solution](src/days/day08.rs)\n- [ ] Day 9\n- [ ] Day 19\n";
+        assert_eq!(
+            "- [x] Day 8 - [Solution](src/days/day08.rs)\n- [x] Day 9 - [Solution](src/days/day09.rs)\n- [ ] Day 19\n",
+            tick_readme(readme, 9).unwrap()
+        );
+        assert_eq!(None, tick_readme(readme, 8));
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::*;
+use std::any::Any;
+use std::env;
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use crate::input::InputSource;
+use crate::runner::{self, DayReport, Options};
+
+/// A single day's puzzle: the input is parsed once and both parts solve from it.
+pub trait Solution {
+    /// Day of the month, 1 to 25.
+    const DAY: u8;
+    /// Puzzle title as shown on the puzzle page.
+    const TITLE: &'static str;
+
+    type Input: Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<usize>;
+
+    fn part2(input: &Self::Input) -> Result<usize>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => bail!("invalid part '{}', expected 1 or 2", s),
+        }
+    }
+}
+
+/// Parsed input of a [`DynSolution`], opaque to everything but the solution itself.
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
+/// Object-safe view of a [`Solution`], so different days can live in one registry.
+pub trait DynSolution: Send + Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn parse(&self, input: &str) -> Result<ParsedInput>;
+
+    /// Solves one part, `input` must come from this solution's [`DynSolution::parse`].
+    fn solve(&self, input: &ParsedInput, part: Part) -> Result<usize>;
+}
+
+/// Adapts a [`Solution`] type into a [`DynSolution`] value.
+pub struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S: Solution> Registered<S> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: Solution> Default for Registered<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Solution> DynSolution for Registered<S> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parse(&self, input: &str) -> Result<ParsedInput> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, input: &ParsedInput, part: Part) -> Result<usize> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .context("parsed input belongs to a different day")?;
+
+        match part {
+            Part::One => S::part1(input),
+            Part::Two => S::part2(input),
+        }
+    }
+}
+
+/// Entry point of the per-day binaries, accepts the same flags as `aoc run`
+/// plus an optional input path (`-` for stdin).
+pub fn run_day<S: Solution + 'static>() -> Result<()> {
+    let (mut options, rest) = Options::parse_args(env::args().skip(1))?;
+    match rest.as_slice() {
+        [] => {}
+        [path] if !path.starts_with("--") => options.input = InputSource::from_arg(path),
+        _ => bail!("unexpected arguments: {}", rest.join(" ")),
+    }
+
+    let reports = runner::run(&[const { &Registered::<S>::new() }], &options)?;
+    reports.into_iter().try_for_each(DayReport::into_result)
+}
@@ -0,0 +1,308 @@
+use anyhow::*;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use crate::answers::{self, AnswerStore};
+use crate::examples::{self, EXAMPLES_DIR};
+use crate::input::{self, InputSource};
+use crate::runner::{self, Options};
+use crate::solution::{DynSolution, Part};
+
+/// How often watched files are polled unless `--interval` says otherwise.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Source file of a day in the crate at `root`.
+pub fn source_path(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("src/days/day{:02}.rs", day))
+}
+
+/// Directory the examples of the crate at `root` are read from.
+pub fn examples_dir(root: &Path) -> PathBuf {
+    root.join(EXAMPLES_DIR)
+}
+
+/// Modification times of everything a day depends on, `None` for missing files.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, Option<SystemTime>>);
+
+impl Snapshot {
+    /// Looks at the input, the day's examples and its source.
+    pub fn take(day: u8, input: &Path, examples_dir: &Path, source: &Path) -> Self {
+        let modified = |path: &Path| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+
+        let prefix = format!("{:02}-", day);
+        let mut paths = vec![input.to_path_buf(), source.to_path_buf()];
+        if let Result::Ok(entries) = fs::read_dir(examples_dir) {
+            paths.extend(
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        path.file_name()
+                            .and_then(|name| name.to_str())
+                            .is_some_and(|name| name.starts_with(&prefix))
+                    }),
+            );
+        }
+
+        Self(paths.into_iter().map(|path| (path.clone(), modified(&path))).collect())
+    }
+
+    /// Paths that were added, removed or modified since `earlier`.
+    pub fn changes(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        let mut changed = self
+            .0
+            .iter()
+            .filter(|(path, modified)| earlier.0.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        changed.extend(earlier.0.keys().filter(|path| !self.0.contains_key(*path)).cloned());
+        changed
+    }
+}
+
+/// Renders answers as `1=143,2=123` for `--previous`.
+pub fn format_previous(previous: &BTreeMap<Part, usize>) -> String {
+    previous
+        .iter()
+        .map(|(part, answer)| format!("{}={}", part, answer))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parses answers written by [`format_previous`].
+pub fn parse_previous(text: &str) -> Result<BTreeMap<Part, usize>> {
+    text.split(',')
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let (part, answer) = item
+                .split_once('=')
+                .with_context(|| format!("invalid previous answer '{}', expected PART=ANSWER", item))?;
+            Ok((part.parse()?, answer.parse()?))
+        })
+        .collect()
+}
+
+/// Solves the day's examples and its input, printing a compact pass/fail
+/// report and how each answer differs from `previous`, which is updated.
+/// Returns whether everything passed.
+pub fn check(
+    solution: &'static dyn DynSolution,
+    options: &Options,
+    examples_dir: &Path,
+    previous: &mut BTreeMap<Part, usize>,
+) -> Result<bool> {
+    let mut passed = true;
+
+    let examples = examples::load(examples_dir, solution.day())?;
+    if examples.is_empty() {
+        println!("examples  none in {}", examples_dir.display());
+    }
+    for example in &examples {
+        let outcomes = examples::check(solution, example)
+            .into_iter()
+            .filter(|outcome| options.part.is_none_or(|part| part == outcome.part))
+            .map(|outcome| {
+                passed &= outcome.passed();
+                match &outcome.actual {
+                    Result::Ok(actual) if outcome.passed() => format!("part {} ✓ {}", outcome.part, actual),
+                    Result::Ok(actual) => {
+                        format!("part {} ✗ {} expected {}", outcome.part, actual, outcome.expected)
+                    }
+                    Err(err) => format!("part {} ✗ error: {:#}", outcome.part, err),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let outcomes = if outcomes.is_empty() {
+            "no expected answers".to_string()
+        } else {
+            outcomes.join("   ")
+        };
+        println!("example   {:<10}{}", example.name, outcomes);
+    }
+
+    let store = AnswerStore::load(options.answers_path())?;
+    let report = runner::run_one(solution, options, &store);
+    passed &= report.is_ok();
+
+    if let Some(err) = &report.error {
+        println!("input     error: {:#}", err);
+    }
+    let answers = report
+        .parts
+        .iter()
+        .map(|part| match (&part.answer, part.status) {
+            (Result::Ok(answer), status) => {
+                let mut line = format!("part {} = {}", part.part, answer);
+                if let Some(status) = status {
+                    line = format!("{} {}", line, status);
+                }
+                match previous.insert(part.part, *answer) {
+                    Some(before) if before != *answer => format!("{}, was {}", line, before),
+                    _ => line,
+                }
+            }
+            (Err(err), _) => format!("part {} ✗ {:#}", part.part, err),
+        })
+        .collect::<Vec<_>>();
+    if !answers.is_empty() {
+        println!("input     {}", answers.join("   "));
+    }
+
+    println!("{}", if passed { "PASS" } else { "FAIL" });
+    Ok(passed)
+}
+
+/// Rebuilds the runner of the crate at `root` with the profile and features
+/// of the running one.
+fn rebuild(root: &Path) -> Result<bool> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(root)
+        .args(["build", "--quiet", "--bin", "aoc"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    if cfg!(feature = "alloc-stats") {
+        command.args(["--features", "alloc-stats"]);
+    }
+
+    let status = command.status().context("failed to run cargo build")?;
+    Ok(status.success())
+}
+
+/// Replaces this process with the freshly built runner at `exe`, continuing
+/// to watch with `args` and the previous answers.
+fn restart(exe: &Path, args: &[String], previous: &BTreeMap<Part, usize>) -> Result<()> {
+    let mut command = Command::new(exe);
+    command.arg("watch").args(args).arg(format!("--previous={}", format_previous(previous)));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        Err(command.exec()).context("failed to restart the runner")
+    }
+
+    #[cfg(not(unix))]
+    {
+        let status = command.status().context("failed to restart the runner")?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Checks the day of the crate at `root` once and again whenever its input,
+/// examples or source change. Source changes rebuild the runner and restart
+/// it with `args`, the arguments `watch` was started with. The default input
+/// and answers are the ones of `root` too. Only returns on errors.
+pub fn watch(
+    root: &Path,
+    solution: &'static dyn DynSolution,
+    options: &Options,
+    interval: Duration,
+    mut previous: BTreeMap<Part, usize>,
+    args: &[String],
+) -> Result<()> {
+    let day = solution.day();
+    let input = match &options.input {
+        InputSource::Default => root.join(input::default_path(day)),
+        InputSource::Path(path) => path.clone(),
+        InputSource::Stdin => bail!("watch needs an input file, not stdin"),
+    };
+    let options = &Options {
+        input: InputSource::Path(input.clone()),
+        answers: Some(options.answers.clone().unwrap_or_else(|| root.join(answers::DEFAULT_PATH))),
+        ..options.clone()
+    };
+    let (examples_dir, source) = (examples_dir(root), source_path(root, day));
+    // once cargo replaced the binary this no longer resolves to its path
+    let exe = env::current_exe().context("failed to locate the running executable")?;
+
+    println!(
+        "Watching {}, {} and {}/{:02}-* (Ctrl-C to stop)",
+        input.display(),
+        source.display(),
+        examples_dir.display(),
+        day
+    );
+
+    let mut snapshot = Snapshot::take(day, &input, &examples_dir, &source);
+    let mut changes = Vec::new();
+    loop {
+        println!();
+        match changes.as_slice() {
+            [] => println!("--- Day {:02}: {} ---", day, solution.title()),
+            changes => println!(
+                "--- Day {:02}: {}, after changes to {} ---",
+                day,
+                solution.title(),
+                changes
+                    .iter()
+                    .map(|path: &PathBuf| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+        if let Err(err) = check(solution, options, &examples_dir, &mut previous) {
+            println!("Error: {:#}", err);
+        }
+
+        changes = loop {
+            thread::sleep(interval);
+            let current = Snapshot::take(day, &input, &examples_dir, &source);
+            let changes = current.changes(&snapshot);
+            snapshot = current;
+
+            if changes.contains(&source) {
+                println!("\n{} changed, rebuilding...", source.display());
+                if rebuild(root)? {
+                    restart(&exe, args, &previous)?;
+                }
+                println!("Build failed, waiting for the next change");
+            } else if !changes.is_empty() {
+                break changes;
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ScratchDir;
+
+    #[test]
+    fn round_trips_previous_answers() {
+        let previous = BTreeMap::from([(Part::One, 143), (Part::Two, 123)]);
+        assert_eq!("1=143,2=123", format_previous(&previous));
+        assert_eq!(previous, parse_previous("1=143,2=123").unwrap());
+        assert_eq!(BTreeMap::new(), parse_previous("").unwrap());
+        assert!(parse_previous("1:143").is_err());
+        assert!(parse_previous("3=1").is_err());
+    }
+
+    #[test]
+    fn detects_changes() {
+        let dir = ScratchDir::new("watch");
+        let (input, source) = (dir.join("05.txt"), dir.join("day05.rs"));
+        fs::write(&input, "1\n").unwrap();
+        fs::write(&source, "").unwrap();
+
+        let before = Snapshot::take(5, &input, dir.path(), &source);
+        assert!(Snapshot::take(5, &input, dir.path(), &source).changes(&before).is_empty());
+
+        let example = dir.join("05-1.txt");
+        fs::write(&example, "1\n").unwrap();
+        fs::write(dir.join("06-1.txt"), "1\n").unwrap();
+        let after = Snapshot::take(5, &input, dir.path(), &source);
+        assert_eq!(vec![example.clone()], after.changes(&before));
+
+        fs::remove_file(&example).unwrap();
+        assert_eq!(vec![example], Snapshot::take(5, &input, dir.path(), &source).changes(&after));
+    }
+}